@@ -1,21 +1,26 @@
-use super::graph::GraphError;
+use super::graph::{CycleError, GraphError};
+use super::measure::Measure;
+use super::paths::{AllPairsShortestPaths, BfsTree, Path, ShortestPaths};
 use super::Graph;
+use crate::unionfind::UnionFind;
 use std::cmp::Reverse;
-use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
 use std::fmt::Debug;
 use std::hash::Hash;
-use std::ops::Add;
 use std::rc::Rc;
 
+/// Identifies a strongly connected component by its index in the list of components.
+pub type ComponentId = usize;
+
 impl<N, E> Graph<N, E>
 where
     N: Hash + Eq + Debug + Ord,
     E: Hash + Eq + Ord,
 {
     /// This function performs Breadth First Search on the graph, starting from the given source node.
-    /// The function returns the predecessors in the form `HashMap<&N, &N>`, where a given N will map
-    /// to its predecessor node. A `GraphError::NodeNotFound` error will be returned if the source node
-    /// does not exist in the map.
+    /// The function returns a `BfsTree`, which dereferences to the predecessors in the form `HashMap<&N, &N>`,
+    /// where a given N will map to its predecessor node. Paths can be found with `BfsTree::path_to`.
+    /// A `GraphError::NodeNotFound` error will be returned if the source node does not exist in the map.
     ///
     /// # Examples
     ///
@@ -39,7 +44,7 @@ where
     ///
     ///
     /// ```
-    pub fn bfs<'a>(&'a self, src: &'a N) -> Result<HashMap<&'a N, &'a N>, GraphError<'a, N>> {
+    pub fn bfs<'a>(&'a self, src: &'a N) -> Result<BfsTree<'a, N, E>, GraphError<'a, N>> {
         let src_rc = match self.nodes.get(src) {
             Some(rc) => rc,
             None => return Err(GraphError::NodeNotFound(src)),
@@ -47,31 +52,28 @@ where
 
         let mut q = VecDeque::new();
         let mut pred = HashMap::new();
+        let mut via = HashMap::new();
 
         pred.insert(&**src_rc, &**src_rc);
 
         q.push_back(&**src_rc);
 
-        loop {
-            let curr = match q.pop_front() {
-                Some(n) => n,
-                None => break,
-            };
-
+        while let Some(curr) = q.pop_front() {
             let curr_edges = match self.edges.get(curr) {
                 Some(set) => set,
-                None => return Err(GraphError::NodeNotFound(&curr)),
+                None => return Err(GraphError::NodeNotFound(curr)),
             };
 
-            for (dst, _) in curr_edges.iter() {
-                if pred.get(&**dst).is_none() {
+            for (dst, weight) in curr_edges.iter() {
+                if !pred.contains_key(&**dst) {
                     pred.insert(dst, curr);
+                    via.insert(&**dst, (curr, weight));
                     q.push_back(dst);
                 }
             }
         }
 
-        Ok(pred)
+        Ok(BfsTree { pred, via })
     }
 
     /// This function performs Depth First Search on the graph from the specified source.
@@ -108,12 +110,7 @@ where
 
         stack.push(src);
 
-        loop {
-            let curr = match stack.pop() {
-                Some(n) => n,
-                None => break,
-            };
-
+        while let Some(curr) = stack.pop() {
             let curr_edges = match self.edges.get(curr) {
                 Some(set) => set,
                 None => return Err(GraphError::NodeNotFound(curr)),
@@ -155,10 +152,8 @@ where
         let mut stack = BTreeSet::new();
 
         for node in self.nodes.iter() {
-            if !visited.contains(node) {
-                if self.explore_for_cycle(node, &mut visited, &mut stack) {
-                    return true;
-                }
+            if !visited.contains(node) && self.explore_for_cycle(node, &mut visited, &mut stack) {
+                return true;
             }
         }
 
@@ -196,35 +191,565 @@ where
 
         false
     }
+
+    /// This function returns a topological ordering of the nodes, i.e. an ordering in which every node
+    /// comes before all of the nodes it has edges to.
+    ///
+    /// If the graph contains a cycle, no such ordering exists and a `CycleError` is returned, holding
+    /// a cycle found in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Lima", "Cusco", "Arequipa");
+    /// g.add_edge(&"Lima", &"Cusco", None);
+    /// g.add_edge(&"Cusco", &"Arequipa", None);
+    ///
+    /// assert_eq!(g.topological_sort(), Ok(vec![&"Lima", &"Cusco", &"Arequipa"]));
+    ///
+    /// g.add_edge(&"Arequipa", &"Lima", None);
+    ///
+    /// let err = g.topological_sort().unwrap_err();
+    /// assert_eq!(err.cycle, vec![&"Arequipa", &"Lima", &"Cusco", &"Arequipa"]);
+    /// ```
+    pub fn topological_sort(&self) -> Result<Vec<&N>, CycleError<'_, N>> {
+        // Nodes that are currently on the stack are `false`, and finished nodes are `true`.
+        let mut finished: HashMap<&N, bool> = HashMap::new();
+        let mut order: Vec<&N> = Vec::new();
+
+        for root in self.nodes.iter() {
+            if finished.contains_key(&**root) {
+                continue;
+            }
+
+            let mut frames = vec![(&**root, self.edges[&**root].iter())];
+            finished.insert(root, false);
+
+            while let Some((curr, curr_edges)) = frames.last_mut() {
+                let curr = *curr;
+
+                match curr_edges.next() {
+                    Some((dst, _)) => match finished.get(&**dst) {
+                        None => {
+                            finished.insert(dst, false);
+                            frames.push((dst, self.edges[&**dst].iter()));
+                        }
+                        Some(false) => {
+                            let start = frames
+                                .iter()
+                                .position(|(n, _)| *n == &**dst)
+                                .expect("Unfinished nodes are on the stack");
+
+                            let mut cycle: Vec<&N> =
+                                frames[start..].iter().map(|(n, _)| *n).collect();
+                            cycle.push(dst);
+
+                            return Err(CycleError { cycle });
+                        }
+                        Some(true) => {}
+                    },
+                    None => {
+                        frames.pop();
+                        finished.insert(curr, true);
+                        order.push(curr);
+                    }
+                }
+            }
+        }
+
+        order.reverse();
+
+        Ok(order)
+    }
+
+    /// This function groups the nodes into topological levels using Kahn's algorithm. Level `0` holds
+    /// the nodes with no incoming edges, and every other node is in the level after the last of its
+    /// predecessors. Nodes in the same level have no edges between them, so they can be processed at
+    /// the same time. The nodes within a level are sorted.
+    ///
+    /// If the graph contains a cycle, a `CycleError` is returned, holding a cycle found in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("fetch", "compile", "lint", "test", "ship");
+    /// g.add_edge(&"fetch", &"compile", None);
+    /// g.add_edge(&"fetch", &"lint", None);
+    /// g.add_edge(&"compile", &"test", None);
+    /// g.add_edge(&"lint", &"ship", None);
+    /// g.add_edge(&"test", &"ship", None);
+    ///
+    /// let levels = g.topological_levels().unwrap();
+    ///
+    /// assert_eq!(levels, vec![vec![&"fetch"], vec![&"compile", &"lint"], vec![&"test"], vec![&"ship"]]);
+    /// ```
+    pub fn topological_levels(&self) -> Result<Vec<Vec<&N>>, CycleError<'_, N>> {
+        let mut in_degree: HashMap<&N, usize> = self.nodes.iter().map(|n| (&**n, 0)).collect();
+
+        for (dst, _) in self.edges.values().flat_map(|set| set.iter()) {
+            *in_degree
+                .get_mut(&**dst)
+                .expect("Every edge destination is a node") += 1;
+        }
+
+        let mut level: Vec<&N> = self
+            .nodes
+            .iter()
+            .map(|n| &**n)
+            .filter(|n| in_degree[n] == 0)
+            .collect();
+
+        let mut levels = Vec::new();
+        let mut seen = 0;
+
+        while !level.is_empty() {
+            let mut next = Vec::new();
+
+            for curr in level.iter() {
+                for (dst, _) in self.edges[*curr].iter() {
+                    let degree = in_degree
+                        .get_mut(&**dst)
+                        .expect("Every edge destination is a node");
+                    *degree -= 1;
+
+                    if *degree == 0 {
+                        next.push(&**dst);
+                    }
+                }
+            }
+
+            next.sort();
+            seen += level.len();
+            levels.push(level);
+            level = next;
+        }
+
+        if seen < self.nodes.len() {
+            return Err(self
+                .topological_sort()
+                .expect_err("Kahn's algorithm only gets stuck on a cycle"));
+        }
+
+        Ok(levels)
+    }
+
+    /// This function finds the strongly connected components of the graph using an iterative
+    /// version of Tarjan's algorithm. It is equivalent to `tarjan_scc`.
+    ///
+    /// The function returns a tuple `(components, component_of)`, in which `components` is of type
+    /// `Vec<Vec<&N>>` and `component_of` is of type `HashMap<&N, ComponentId>`, mapping each node to the
+    /// index of its component in `components`. Components are ordered topologically, i.e. if there
+    /// is an edge from a node in component `i` to a node in component `j`, then `i <= j`.
+    /// The nodes within a component are sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Oslo", "Bergen", "Trondheim", "Stavanger");
+    /// g.add_edge(&"Oslo", &"Bergen", None);
+    /// g.add_edge(&"Bergen", &"Oslo", None);
+    /// g.add_edge(&"Bergen", &"Trondheim", None);
+    /// g.add_edge(&"Trondheim", &"Stavanger", None);
+    /// g.add_edge(&"Stavanger", &"Trondheim", None);
+    ///
+    /// let (components, component_of) = g.strongly_connected_components();
+    ///
+    /// assert_eq!(components, vec![vec![&"Bergen", &"Oslo"], vec![&"Stavanger", &"Trondheim"]]);
+    /// assert_eq!(*component_of.get(&"Oslo").unwrap(), 0);
+    /// assert_eq!(*component_of.get(&"Stavanger").unwrap(), 1);
+    /// ```
+    pub fn strongly_connected_components(&self) -> (Vec<Vec<&N>>, HashMap<&N, ComponentId>) {
+        self.tarjan_scc()
+    }
+
+    /// This function finds the strongly connected components of the graph using Tarjan's algorithm.
+    /// The depth first search is performed with an explicit stack, so large graphs will not overflow
+    /// the call stack.
+    ///
+    /// The return value is the same as `strongly_connected_components`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4);
+    /// g.add_edge(&1, &2, None);
+    /// g.add_edge(&2, &3, None);
+    /// g.add_edge(&3, &1, None);
+    /// g.add_edge(&4, &1, None);
+    ///
+    /// let (components, component_of) = g.tarjan_scc();
+    ///
+    /// assert_eq!(components, vec![vec![&4], vec![&1, &2, &3]]);
+    /// assert_eq!(component_of.get(&1), component_of.get(&3));
+    /// ```
+    pub fn tarjan_scc(&self) -> (Vec<Vec<&N>>, HashMap<&N, ComponentId>) {
+        let mut index: HashMap<&N, usize> = HashMap::new();
+        let mut lowlink: HashMap<&N, usize> = HashMap::new();
+        let mut on_stack: HashSet<&N> = HashSet::new();
+        let mut stack: Vec<&N> = Vec::new();
+        let mut components: Vec<Vec<&N>> = Vec::new();
+
+        for root in self.nodes.iter() {
+            if index.contains_key(&**root) {
+                continue;
+            }
+
+            // Each frame holds a node along with the edges that are still left to explore from it.
+            let mut frames = vec![(&**root, self.edges[&**root].iter())];
+            let i = index.len();
+            index.insert(root, i);
+            lowlink.insert(root, i);
+            stack.push(root);
+            on_stack.insert(root);
+
+            while let Some((curr, curr_edges)) = frames.last_mut() {
+                let curr = *curr;
+
+                if let Some((dst, _)) = curr_edges.next() {
+                    let dst = &**dst;
+
+                    if !index.contains_key(dst) {
+                        let i = index.len();
+                        index.insert(dst, i);
+                        lowlink.insert(dst, i);
+                        stack.push(dst);
+                        on_stack.insert(dst);
+                        frames.push((dst, self.edges[dst].iter()));
+                    } else if on_stack.contains(dst) {
+                        let low = lowlink[curr].min(index[dst]);
+                        lowlink.insert(curr, low);
+                    }
+
+                    continue;
+                }
+
+                frames.pop();
+
+                if let Some((parent, _)) = frames.last() {
+                    let low = lowlink[parent].min(lowlink[curr]);
+                    lowlink.insert(parent, low);
+                }
+
+                if lowlink[curr] == index[curr] {
+                    let mut component = Vec::new();
+
+                    while let Some(n) = stack.pop() {
+                        on_stack.remove(n);
+                        component.push(n);
+
+                        if n == curr {
+                            break;
+                        }
+                    }
+
+                    component.sort();
+                    components.push(component);
+                }
+            }
+        }
+
+        // Tarjan's algorithm finds components in reverse topological order.
+        components.reverse();
+
+        let component_of = Self::component_map(&components);
+
+        (components, component_of)
+    }
+
+    /// This function finds the strongly connected components of the graph using Kosaraju's algorithm.
+    /// Both passes of depth first search are performed with an explicit stack.
+    ///
+    /// The return value is the same as `strongly_connected_components`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4);
+    /// g.add_edge(&1, &2, None);
+    /// g.add_edge(&2, &3, None);
+    /// g.add_edge(&3, &1, None);
+    /// g.add_edge(&4, &1, None);
+    ///
+    /// let (components, component_of) = g.kosaraju_scc();
+    ///
+    /// assert_eq!(components, vec![vec![&4], vec![&1, &2, &3]]);
+    /// assert_eq!(*component_of.get(&4).unwrap(), 0);
+    /// ```
+    pub fn kosaraju_scc(&self) -> (Vec<Vec<&N>>, HashMap<&N, ComponentId>) {
+        // First pass: record the order in which nodes finish.
+        let mut visited: HashSet<&N> = HashSet::new();
+        let mut finished: Vec<&N> = Vec::new();
+
+        for root in self.nodes.iter() {
+            if !visited.insert(&**root) {
+                continue;
+            }
+
+            let mut frames = vec![(&**root, self.edges[&**root].iter())];
+
+            while let Some((curr, curr_edges)) = frames.last_mut() {
+                let curr = *curr;
+
+                match curr_edges.next() {
+                    Some((dst, _)) => {
+                        if visited.insert(&**dst) {
+                            frames.push((&**dst, self.edges[&**dst].iter()));
+                        }
+                    }
+                    None => {
+                        frames.pop();
+                        finished.push(curr);
+                    }
+                }
+            }
+        }
+
+        // Second pass: explore the reversed graph in decreasing finish order.
+        let reversed = self.reverse_adjacency();
+        let mut assigned: HashSet<&N> = HashSet::new();
+        let mut components: Vec<Vec<&N>> = Vec::new();
+
+        for root in finished.into_iter().rev() {
+            if !assigned.insert(root) {
+                continue;
+            }
+
+            let mut component = Vec::new();
+            let mut stack = vec![root];
+
+            while let Some(curr) = stack.pop() {
+                component.push(curr);
+
+                for src in reversed[curr].iter() {
+                    if assigned.insert(src) {
+                        stack.push(src);
+                    }
+                }
+            }
+
+            component.sort();
+            components.push(component);
+        }
+
+        let component_of = Self::component_map(&components);
+
+        (components, component_of)
+    }
+
+    /// This function builds the condensation of the graph, in which every strongly connected component
+    /// is contracted into a single node. The condensation is always acyclic.
+    ///
+    /// The nodes of the new graph are the `ComponentId`s returned by `strongly_connected_components`,
+    /// so counting up from `0` gives a valid topological order. For each pair of distinct components,
+    /// the weights of all edges between them are passed to `merge`, and a single edge is added with the
+    /// weight that it returns.
+    ///
+    /// The function returns a tuple `(condensed, components)`, in which `components` maps each
+    /// `ComponentId` to the nodes it contains.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("core", "utils", "net", "app");
+    /// g.add_edge(&"core", &"utils", Some(2));
+    /// g.add_edge(&"utils", &"core", Some(1));
+    /// g.add_edge(&"core", &"net", Some(4));
+    /// g.add_edge(&"utils", &"net", Some(3));
+    /// g.add_edge(&"net", &"app", None);
+    ///
+    /// // Keep the smallest weight between two components
+    /// let (condensed, components) = g.condensation(|weights| weights.into_iter().flatten().min().cloned());
+    ///
+    /// assert_eq!(components, vec![vec![&"core", &"utils"], vec![&"net"], vec![&"app"]]);
+    /// assert_eq!(condensed.num_nodes(), 3);
+    /// assert_eq!(condensed.num_edges(), 2);
+    /// assert!(condensed.is_edge(&0, &1, &Some(3)));
+    /// assert!(condensed.is_edge(&1, &2, &None));
+    /// assert!(!condensed.has_cycle());
+    /// ```
+    pub fn condensation<W, F>(&self, mut merge: F) -> (Graph<ComponentId, W>, Vec<Vec<&N>>)
+    where
+        W: Hash + Eq + Ord,
+        F: FnMut(Vec<&Option<E>>) -> Option<W>,
+    {
+        let (components, component_of) = self.strongly_connected_components();
+
+        let mut between: BTreeMap<(ComponentId, ComponentId), Vec<&Option<E>>> = BTreeMap::new();
+
+        for (src, set) in self.edges.iter() {
+            for (dst, weight) in set.iter() {
+                let (src_id, dst_id) = (component_of[&**src], component_of[&**dst]);
+
+                if src_id != dst_id {
+                    between.entry((src_id, dst_id)).or_default().push(weight);
+                }
+            }
+        }
+
+        let mut condensed = Graph::new();
+
+        for id in 0..components.len() {
+            condensed.add_node(id);
+        }
+
+        for ((src_id, dst_id), weights) in between {
+            condensed.add_edge(&src_id, &dst_id, merge(weights));
+        }
+
+        (condensed, components)
+    }
+
+    /// Maps every node to its incoming neighbours. Parallel edges result in repeated neighbours.
+    pub(crate) fn reverse_adjacency(&self) -> HashMap<&N, Vec<&N>> {
+        let mut reversed: HashMap<&N, Vec<&N>> =
+            self.nodes.iter().map(|n| (&**n, Vec::new())).collect();
+
+        for (src, set) in self.edges.iter() {
+            for (dst, _) in set.iter() {
+                reversed
+                    .get_mut(&**dst)
+                    .expect("Every edge destination is a node")
+                    .push(src);
+            }
+        }
+
+        reversed
+    }
+
+    /// Maps every node to its neighbours when the direction of every edge is ignored. Parallel edges
+    /// are merged, and loops are left out.
+    pub(crate) fn undirected_adjacency(&self) -> HashMap<&N, BTreeSet<&N>> {
+        let mut adjacent: HashMap<&N, BTreeSet<&N>> =
+            self.nodes.iter().map(|n| (&**n, BTreeSet::new())).collect();
+
+        for (src, set) in self.edges.iter() {
+            for (dst, _) in set.iter() {
+                if src != dst {
+                    adjacent.entry(&**src).or_default().insert(&**dst);
+                    adjacent.entry(&**dst).or_default().insert(&**src);
+                }
+            }
+        }
+
+        adjacent
+    }
+
+    /// Returns every edge of the graph as an undirected edge `(u, v, weight)` with `u < v`, so that the
+    /// two directed edges added by `add_undirected_edge` become one. Loops are left out.
+    pub(crate) fn undirected_edges(&self) -> BTreeSet<(&N, &N, &Option<E>)> {
+        let mut undirected = BTreeSet::new();
+
+        for (src, set) in self.edges.iter() {
+            for (dst, e) in set.iter() {
+                let (src, dst) = (&**src, &**dst);
+
+                if src < dst {
+                    undirected.insert((src, dst, e));
+                } else if dst < src {
+                    undirected.insert((dst, src, e));
+                }
+            }
+        }
+
+        undirected
+    }
+
+    /// This function finds the weakly connected components of the graph, i.e. the connected components
+    /// when the direction of every edge is ignored. It uses a `UnionFind`, so it runs in close to
+    /// `O(V + E)` time.
+    ///
+    /// The function returns a tuple `(components, component_of)`, in the same form as
+    /// `strongly_connected_components`. Components are ordered by their smallest node, and the nodes
+    /// within a component are sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Maui", "Oahu", "Kauai", "Lanai", "Molokai");
+    /// g.add_edge(&"Maui", &"Lanai", None);
+    /// g.add_edge(&"Molokai", &"Maui", None);
+    /// g.add_edge(&"Oahu", &"Kauai", None);
+    ///
+    /// let (components, component_of) = g.weakly_connected_components();
+    ///
+    /// assert_eq!(components, vec![vec![&"Kauai", &"Oahu"], vec![&"Lanai", &"Maui", &"Molokai"]]);
+    /// assert_eq!(*component_of.get(&"Molokai").unwrap(), 1);
+    /// ```
+    pub fn weakly_connected_components(&self) -> (Vec<Vec<&N>>, HashMap<&N, ComponentId>) {
+        let mut sets = UnionFind::new();
+
+        for n in self.nodes.iter() {
+            sets.make_set(&**n);
+        }
+
+        for (src, set) in self.edges.iter() {
+            for (dst, _) in set.iter() {
+                sets.union(&&**src, &&**dst);
+            }
+        }
+
+        // Nodes were added in sorted order, so each set is sorted and sets are ordered by their smallest node.
+        let components: Vec<Vec<&N>> = sets
+            .sets()
+            .into_iter()
+            .map(|set| set.into_iter().copied().collect())
+            .collect();
+
+        let component_of = Self::component_map(&components);
+
+        (components, component_of)
+    }
+
+    fn component_map<'a>(components: &[Vec<&'a N>]) -> HashMap<&'a N, ComponentId> {
+        components
+            .iter()
+            .enumerate()
+            .flat_map(|(i, component)| component.iter().map(move |n| (*n, i)))
+            .collect()
+    }
 }
 
 impl<N, E> Graph<N, E>
 where
     N: Hash + Eq + Ord + Debug,
-    E: Hash + Eq + Ord + Add<Output = E> + Clone,
+    E: Hash + Eq + Ord + Measure,
 {
     /// This function performs Djikstra's algorithm on the graph, beginning from the source node.
-    /// The parameter `default_weight` is the weight that will be used for unweighted edges,
-    /// and `zero` is the distance value for the source.
+    /// Unweighted edges have a weight of `Measure::default_weight`, and the source has a distance of `Measure::zero`.
     ///
-    /// The function returns a tuple `(dist, pred)`, in which `dist` is of type `HashMap<&N, E>`, mapping
-    /// nodes to their total distances from the source. `pred` is of type `HashMap<&N, Option<&N>>`, mapping
-    /// nodes to their predecessors, where the predecessor to the source is `None`.
-    /// `GraphError::NodeNotFound` is returned if the src node doesn't exist.
+    /// The function returns a `ShortestPaths`, holding `dist` of type `HashMap<&N, E>`, mapping
+    /// nodes to their total distances from the source, and `pred` of type `HashMap<&N, Option<&N>>`, mapping
+    /// nodes to their predecessors, where the predecessor to the source is `None`. Paths can be found
+    /// with `ShortestPaths::path_to`. `GraphError::NodeNotFound` is returned if the src node doesn't exist.
     ///
     /// # Examples
     ///
     /// ```
-    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    /// use ferrisgraph::graph::{Graph, paths::ShortestPaths};
+    /// use ferrisgraph::graph_with_nodes;
     /// let mut g: Graph<&str, i32> = graph_with_nodes!("Sydney", "Melbourne", "Perth");
     ///
     /// g.add_undirected_edge(&"Sydney", &"Melbourne", Some(7));
     /// g.add_undirected_edge(&"Melbourne", &"Perth", Some(5));
     /// g.add_undirected_edge(&"Sydney", &"Perth", Some(15));
     ///
-    /// let res = g.djikstra(&"Sydney", 1, 0).unwrap();
+    /// let res = g.djikstra(&"Sydney").unwrap();
     ///
-    /// let (dist, pred) = res;
+    /// let ShortestPaths { dist, pred, .. } = res;
     ///
     /// assert_eq!(*dist.get(&"Melbourne").unwrap(), 7);
     /// assert_eq!(*dist.get(&"Perth").unwrap(), 12);
@@ -237,43 +762,528 @@ where
     pub fn djikstra<'a>(
         &'a self,
         src: &'a N,
-        default_weight: E,
-        zero: E,
-    ) -> Result<(HashMap<&'a N, E>, HashMap<&'a N, Option<&'a N>>), GraphError<'a, N>> {
+    ) -> Result<ShortestPaths<'a, N, E>, GraphError<'a, N>> {
+        let src = match self.nodes.get(src) {
+            Some(rc) => &**rc,
+            None => return Err(GraphError::NodeNotFound(src)),
+        };
+
+        Ok(self.djikstra_with_weights(src, |_, _, e| e.clone().unwrap_or_else(E::default_weight)))
+    }
+
+    /// This function performs A* search on the graph, finding the shortest path from `src` to `dst`.
+    /// `heuristic` estimates the distance from a node to `dst`, and must never overestimate it for the
+    /// result to be the shortest path. The search stops as soon as `dst` is reached, so it usually explores
+    /// far fewer nodes than `djikstra`. Unweighted edges are treated the same as in `djikstra`.
+    ///
+    /// The function returns the shortest `Path` from `src` to `dst`, and `None` if there is no path.
+    /// `GraphError::NodeNotFound` is returned if either node doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    /// let mut g: Graph<(i32, i32), i32> = graph_with_nodes!((0, 0), (0, 3), (4, 0), (4, 3));
+    ///
+    /// g.add_undirected_edge(&(0, 0), &(0, 3), Some(3));
+    /// g.add_undirected_edge(&(0, 0), &(4, 0), Some(4));
+    /// g.add_undirected_edge(&(0, 3), &(4, 3), Some(4));
+    /// g.add_undirected_edge(&(4, 0), &(4, 3), Some(5));
+    ///
+    /// // Manhattan distance never overestimates on this grid
+    /// let manhattan = |n: &(i32, i32)| (4 - n.0).abs() + (3 - n.1).abs();
+    ///
+    /// let path = g.astar(&(0, 0), &(4, 3), manhattan).unwrap().unwrap();
+    ///
+    /// assert_eq!(path.nodes, vec![&(0, 0), &(0, 3), &(4, 3)]);
+    /// assert_eq!(path.cost, 7);
+    /// ```
+    pub fn astar<'a, H>(
+        &'a self,
+        src: &'a N,
+        dst: &'a N,
+        heuristic: H,
+    ) -> Result<Option<Path<'a, N, E>>, GraphError<'a, N>>
+    where
+        H: Fn(&N) -> E,
+    {
+        let src = match self.nodes.get(src) {
+            Some(rc) => &**rc,
+            None => return Err(GraphError::NodeNotFound(src)),
+        };
+
+        let dst = match self.nodes.get(dst) {
+            Some(rc) => &**rc,
+            None => return Err(GraphError::NodeNotFound(dst)),
+        };
+
+        let mut dist: HashMap<&N, E> = HashMap::new();
+        let mut via: HashMap<&N, (&N, &Option<E>)> = HashMap::new();
+
+        // Entries are ordered by estimated total cost, and also hold the distance from the source
+        // when they were pushed so that outdated entries can be skipped.
+        let mut pq = BinaryHeap::new();
+        dist.insert(src, E::zero());
+        pq.push((Reverse(heuristic(src)), src, E::zero()));
+
+        while let Some((_, u, curr_dist)) = pq.pop() {
+            if dist.get(u).is_some_and(|d| *d < curr_dist) {
+                continue;
+            }
+
+            if u == dst {
+                return Ok(Some(Path::walk_back(dst, &via, curr_dist)));
+            }
+
+            for (n, e) in self.edges[u].iter() {
+                let weight = e.clone().unwrap_or_else(E::default_weight);
+
+                let new_dist = curr_dist.saturating_add(&weight);
+
+                if dist.get(&**n).is_none_or(|d| new_dist < *d) {
+                    dist.insert(n, new_dist.clone());
+                    via.insert(n, (u, e));
+                    pq.push((Reverse(new_dist.saturating_add(&heuristic(n))), n, new_dist));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Runs Djikstra's algorithm from a node that is known to exist, using `weight` to find the weight
+    /// of each edge `(src, dst, e)`. All weights returned must be non-negative.
+    fn djikstra_with_weights<'a, F>(&'a self, src: &'a N, weight: F) -> ShortestPaths<'a, N, E>
+    where
+        F: Fn(&N, &N, &Option<E>) -> E,
+    {
         let mut dist: HashMap<&N, E> = HashMap::new();
         let mut pred: HashMap<&N, Option<&N>> = HashMap::new();
+        let mut via: HashMap<&N, (&N, &Option<E>)> = HashMap::new();
 
-        let mut pq = std::collections::BinaryHeap::new();
+        let mut pq = BinaryHeap::new();
         pred.insert(src, None);
-        dist.insert(src, zero.clone());
-        pq.push((Reverse(zero), src));
+        dist.insert(src, E::zero());
+        pq.push((Reverse(E::zero()), src));
 
         while let Some((Reverse(curr_dist), u)) = pq.pop() {
-            if dist.get(u).is_some() && *dist.get(u).unwrap() < curr_dist {
+            if dist.get(u).is_some_and(|d| *d < curr_dist) {
                 continue;
             }
 
-            let u_edges = match self.edges.get(u) {
-                Some(set) => set,
-                None => return Err(GraphError::NodeNotFound(u)),
-            };
-
-            for (n, e) in u_edges {
-                let weight = match e {
-                    Some(x) => x.clone(),
-                    None => default_weight.clone(),
-                };
-
-                let new_dist = weight + curr_dist.clone();
+            for (n, e) in self.edges[u].iter() {
+                let new_dist = curr_dist.saturating_add(&weight(u, n, e));
 
-                if dist.get(&**n).is_none() || new_dist < *dist.get(&**n).unwrap() {
+                if dist.get(&**n).is_none_or(|d| new_dist < *d) {
                     dist.insert(n, new_dist.clone());
                     pred.insert(n, Some(u));
+                    via.insert(n, (u, e));
                     pq.push((Reverse(new_dist), n))
                 }
             }
         }
 
-        Ok((dist, pred))
+        ShortestPaths { dist, pred, via }
+    }
+
+    /// This function performs the Bellman-Ford algorithm on the graph, beginning from the source node.
+    /// Unlike `djikstra`, negative weights are allowed. Unweighted edges are treated the same as in `djikstra`.
+    ///
+    /// The function returns a `ShortestPaths`, which is the same as the one returned by `djikstra`.
+    /// `GraphError::NodeNotFound` is returned if the src node doesn't exist, and `GraphError::NegativeCycle`
+    /// is returned if a cycle with negative total weight is reachable from the source. The cycle is given
+    /// as a path that starts and ends at the same node, e.g. `[a, b, c, a]`.
+    ///
+    /// Distances that would be larger than the largest value of the weight type saturate, as in `djikstra`.
+    /// Distances that would be smaller than the smallest value can't be saturated without hiding negative
+    /// cycles, so they return `GraphError::NegativeCycle` if they are caused by one, and
+    /// `GraphError::WeightOverflow` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::graph::{Graph, graph::GraphError};
+    /// use ferrisgraph::graph_with_nodes;
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("USD", "EUR", "GBP");
+    ///
+    /// g.add_edge(&"USD", &"EUR", Some(4));
+    /// g.add_edge(&"EUR", &"GBP", Some(-3));
+    /// g.add_edge(&"USD", &"GBP", Some(2));
+    ///
+    /// let paths = g.bellman_ford(&"USD").unwrap();
+    ///
+    /// assert_eq!(*paths.dist.get(&"GBP").unwrap(), 1);
+    /// assert_eq!(*paths.pred.get(&"GBP").unwrap(), Some(&"EUR"));
+    /// assert_eq!(paths.path_to(&"GBP").unwrap().nodes, vec![&"USD", &"EUR", &"GBP"]);
+    ///
+    /// g.add_edge(&"GBP", &"EUR", Some(2));
+    ///
+    /// let res = g.bellman_ford(&"USD");
+    /// assert_eq!(res.unwrap_err(), GraphError::NegativeCycle(vec![&"GBP", &"EUR", &"GBP"]));
+    /// ```
+    pub fn bellman_ford<'a>(
+        &'a self,
+        src: &'a N,
+    ) -> Result<ShortestPaths<'a, N, E>, GraphError<'a, N>> {
+        let src = match self.nodes.get(src) {
+            Some(rc) => &**rc,
+            None => return Err(GraphError::NodeNotFound(src)),
+        };
+
+        self.bellman_ford_from(vec![src])
+    }
+
+    /// Runs the Bellman-Ford algorithm with every node in `sources` starting at a distance of zero.
+    /// Starting from every node in the graph finds potentials for Johnson's algorithm, and detects
+    /// negative cycles anywhere in the graph.
+    fn bellman_ford_from<'a>(
+        &'a self,
+        sources: Vec<&'a N>,
+    ) -> Result<ShortestPaths<'a, N, E>, GraphError<'a, N>> {
+        let mut dist: HashMap<&N, E> = HashMap::new();
+        let mut pred: HashMap<&N, Option<&N>> = HashMap::new();
+        let mut via: HashMap<&N, (&N, &Option<E>)> = HashMap::new();
+
+        for src in sources {
+            dist.insert(src, E::zero());
+            pred.insert(src, None);
+        }
+
+        // A shortest path has at most |V| - 1 edges, so an edge that can still be relaxed
+        // on pass |V| means there is a negative cycle.
+        let mut relaxed = None;
+
+        for _ in 0..self.nodes.len() {
+            relaxed = None;
+
+            for (u, u_edges) in self.edges.iter() {
+                let u_dist = match dist.get(&**u) {
+                    Some(d) => d.clone(),
+                    None => continue,
+                };
+
+                for (v, e) in u_edges.iter() {
+                    let weight = e.clone().unwrap_or_else(E::default_weight);
+
+                    let new_dist = match u_dist.checked_add(&weight) {
+                        Some(d) => d,
+                        None if weight >= E::zero() => u_dist.saturating_add(&weight),
+                        None => return Err(self.negative_cycle(&pred, u)),
+                    };
+
+                    if dist.get(&**v).is_none_or(|d| new_dist < *d) {
+                        dist.insert(v, new_dist);
+                        pred.insert(v, Some(u));
+                        via.insert(v, (u, e));
+                        relaxed = Some(&**v);
+                    }
+                }
+            }
+
+            if relaxed.is_none() {
+                break;
+            }
+        }
+
+        match relaxed {
+            Some(v) => Err(self.negative_cycle(&pred, v)),
+            None => Ok(ShortestPaths { dist, pred, via }),
+        }
+    }
+
+    /// Finds the negative cycle that the predecessors of `start` lead to, or returns
+    /// `GraphError::WeightOverflow` if they lead back to a source instead.
+    fn negative_cycle<'a>(
+        &'a self,
+        pred: &HashMap<&'a N, Option<&'a N>>,
+        start: &'a N,
+    ) -> GraphError<'a, N> {
+        let mut curr = start;
+
+        // Walking back |V| times without reaching a source guarantees that we end up on the cycle.
+        for _ in 0..self.nodes.len() {
+            match pred[curr] {
+                Some(prev) => curr = prev,
+                None => return GraphError::WeightOverflow,
+            }
+        }
+
+        let mut cycle = vec![curr];
+        let mut prev = pred[curr].expect("Nodes on a negative cycle have predecessors");
+
+        while prev != curr {
+            cycle.push(prev);
+            prev = pred[prev].expect("Nodes on a negative cycle have predecessors");
+        }
+
+        cycle.push(curr);
+        cycle.reverse();
+
+        GraphError::NegativeCycle(cycle)
+    }
+
+    /// This function performs the Floyd-Warshall algorithm on the graph, finding the shortest paths
+    /// between every pair of nodes. It runs in `O(|V|^3)` time, so it is best suited to small, dense graphs.
+    /// Negative weights are allowed. Unweighted edges are treated the same as in `djikstra`.
+    ///
+    /// The function returns an `AllPairsShortestPaths`. Its `dist` is of type `HashMap<&N, HashMap<&N, E>>`, where
+    /// `dist[u][v]` is the length of the shortest path from `u` to `v`. Its `next` is of type
+    /// `HashMap<&N, HashMap<&N, &N>>`, where `next[u][v]` is the node after `u` on the shortest path to `v`.
+    /// Pairs of nodes with no path between them are missing from both maps. Paths can be found with
+    /// `AllPairsShortestPaths::path`.
+    /// `GraphError::NegativeCycle` is returned if the graph contains a cycle with negative total weight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Sydney", "Melbourne", "Perth");
+    ///
+    /// g.add_undirected_edge(&"Sydney", &"Melbourne", Some(7));
+    /// g.add_undirected_edge(&"Melbourne", &"Perth", Some(5));
+    /// g.add_undirected_edge(&"Sydney", &"Perth", Some(15));
+    ///
+    /// let paths = g.floyd_warshall().unwrap();
+    ///
+    /// assert_eq!(paths.dist[&"Perth"][&"Sydney"], 12);
+    /// assert_eq!(paths.dist[&"Sydney"][&"Sydney"], 0);
+    /// assert_eq!(paths.next[&"Perth"][&"Sydney"], &"Melbourne");
+    /// assert_eq!(paths.next[&"Melbourne"][&"Sydney"], &"Sydney");
+    /// ```
+    pub fn floyd_warshall(&self) -> Result<AllPairsShortestPaths<'_, N, E>, GraphError<'_, N>> {
+        let nodes: Vec<&N> = self.nodes.iter().map(|n| &**n).collect();
+        let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let n = nodes.len();
+
+        let mut dist: Vec<Vec<Option<E>>> = vec![vec![None; n]; n];
+        let mut next: Vec<Vec<Option<usize>>> = vec![vec![None; n]; n];
+        let mut first: Vec<Vec<Option<&Option<E>>>> = vec![vec![None; n]; n];
+
+        for i in 0..n {
+            dist[i][i] = Some(E::zero());
+            next[i][i] = Some(i);
+        }
+
+        for (u, u_edges) in self.edges.iter() {
+            let i = index[&**u];
+
+            for (v, e) in u_edges.iter() {
+                let j = index[&**v];
+
+                let weight = e.clone().unwrap_or_else(E::default_weight);
+
+                if dist[i][j].as_ref().is_none_or(|d| weight < *d) {
+                    dist[i][j] = Some(weight);
+                    next[i][j] = Some(j);
+                    first[i][j] = Some(e);
+                }
+            }
+        }
+
+        for k in 0..n {
+            for i in 0..n {
+                let to_k = match &dist[i][k] {
+                    Some(d) => d.clone(),
+                    None => continue,
+                };
+
+                for j in 0..n {
+                    let new_dist = match &dist[k][j] {
+                        Some(d) => to_k.saturating_add(d),
+                        None => continue,
+                    };
+
+                    if dist[i][j].as_ref().is_none_or(|d| new_dist < *d) {
+                        dist[i][j] = Some(new_dist);
+                        next[i][j] = next[i][k];
+                        first[i][j] = first[i][k];
+                    }
+                }
+            }
+        }
+
+        // A node with a negative distance to itself lies on a negative cycle, which
+        // Bellman-Ford can then recover.
+        if let Some(i) = (0..n).find(|i| dist[*i][*i].as_ref().is_some_and(|d| *d < E::zero())) {
+            return match self.bellman_ford(nodes[i]) {
+                Err(e) => Err(e),
+                Ok(_) => unreachable!("Bellman-Ford finds the same negative cycle"),
+            };
+        }
+
+        let mut paths = AllPairsShortestPaths {
+            dist: HashMap::new(),
+            next: HashMap::new(),
+            first_edge: HashMap::new(),
+        };
+
+        for (i, row) in dist.into_iter().enumerate() {
+            let row: HashMap<&N, E> = row
+                .into_iter()
+                .enumerate()
+                .filter_map(|(j, d)| d.map(|d| (nodes[j], d)))
+                .collect();
+
+            paths.dist.insert(nodes[i], row);
+        }
+
+        for (i, row) in next.into_iter().enumerate() {
+            let row: HashMap<&N, &N> = row
+                .into_iter()
+                .enumerate()
+                .filter_map(|(j, k)| k.map(|k| (nodes[j], nodes[k])))
+                .collect();
+
+            paths.next.insert(nodes[i], row);
+        }
+
+        for (i, row) in first.into_iter().enumerate() {
+            let row: HashMap<&N, &Option<E>> = row
+                .into_iter()
+                .enumerate()
+                .filter_map(|(j, e)| e.map(|e| (nodes[j], e)))
+                .collect();
+
+            paths.first_edge.insert(nodes[i], row);
+        }
+
+        Ok(paths)
+    }
+
+    /// This function performs Johnson's algorithm on the graph, finding the shortest paths between
+    /// every pair of nodes. Edges are reweighted using Bellman-Ford so that they are non-negative,
+    /// and then `djikstra` is run from every node, making it well suited to large, sparse graphs
+    /// with negative weights. Unweighted edges are treated the same as in `djikstra`.
+    ///
+    /// The function returns an `AllPairsShortestPaths`, which is the same as the one returned by `floyd_warshall`.
+    /// `GraphError::NegativeCycle` is returned if the graph contains a cycle with negative total weight, and
+    /// `GraphError::WeightOverflow` if a reweighted edge or distance doesn't fit in the weight type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Cairo", "Giza", "Luxor", "Aswan");
+    ///
+    /// g.add_edge(&"Cairo", &"Giza", Some(3));
+    /// g.add_edge(&"Giza", &"Luxor", Some(-2));
+    /// g.add_edge(&"Cairo", &"Luxor", Some(2));
+    /// g.add_edge(&"Luxor", &"Aswan", None);
+    ///
+    /// let paths = g.johnson().unwrap();
+    ///
+    /// assert_eq!(paths.dist[&"Cairo"][&"Luxor"], 1);
+    /// assert_eq!(paths.dist[&"Cairo"][&"Aswan"], 2);
+    /// assert_eq!(paths.next[&"Cairo"][&"Aswan"], &"Giza");
+    /// assert!(paths.path(&"Aswan", &"Cairo").is_none());
+    ///
+    /// let path = paths.path(&"Cairo", &"Aswan").unwrap();
+    /// assert_eq!(path.nodes, vec![&"Cairo", &"Giza", &"Luxor", &"Aswan"]);
+    /// assert_eq!(path.edges[2], (&"Luxor", &"Aswan", &None));
+    /// ```
+    pub fn johnson(&self) -> Result<AllPairsShortestPaths<'_, N, E>, GraphError<'_, N>> {
+        let sources: Vec<&N> = self.nodes.iter().map(|n| &**n).collect();
+        let potential = self.bellman_ford_from(sources)?.dist;
+
+        // w(u, v) + h(u) - h(v) is never negative, as h holds shortest distances, but it may not fit in the
+        // weight type, so every reweighted edge is checked before running `djikstra`.
+        for (u, u_edges) in self.edges.iter() {
+            for (v, e) in u_edges.iter() {
+                let weight = e.clone().unwrap_or_else(E::default_weight);
+
+                if Self::reweight(&weight, &potential[&**u], &potential[&**v]).is_none() {
+                    return Err(GraphError::WeightOverflow);
+                }
+            }
+        }
+
+        let mut paths = AllPairsShortestPaths {
+            dist: HashMap::new(),
+            next: HashMap::new(),
+            first_edge: HashMap::new(),
+        };
+
+        for src in self.nodes.iter() {
+            let src = &**src;
+
+            let tree = self.djikstra_with_weights(src, |u, v, e| {
+                let weight = e.clone().unwrap_or_else(E::default_weight);
+
+                Self::reweight(&weight, &potential[u], &potential[v])
+                    .expect("Reweighted edges were checked")
+            });
+
+            let row: HashMap<&N, E> = tree
+                .dist
+                .into_iter()
+                .map(|(dst, d)| {
+                    Self::reweight(&d, &potential[dst], &potential[src])
+                        .map(|d| (dst, d))
+                        .ok_or(GraphError::WeightOverflow)
+                })
+                .collect::<Result<_, _>>()?;
+
+            let first = Self::first_hops(src, &tree.via);
+
+            let mut next_row: HashMap<&N, &N> =
+                first.iter().map(|(dst, (hop, _))| (*dst, *hop)).collect();
+            next_row.insert(src, src);
+
+            paths.dist.insert(src, row);
+            paths.next.insert(src, next_row);
+            paths.first_edge.insert(
+                src,
+                first.into_iter().map(|(dst, (_, e))| (dst, e)).collect(),
+            );
+        }
+
+        Ok(paths)
+    }
+
+    /// Finds `weight + add - sub`, or `None` if it doesn't fit in the weight type. Both orders of the two
+    /// steps are tried, since one of them can overflow when the other doesn't.
+    fn reweight(weight: &E, add: &E, sub: &E) -> Option<E> {
+        weight
+            .checked_add(add)
+            .and_then(|w| w.checked_sub(sub))
+            .or_else(|| weight.checked_sub(sub).and_then(|w| w.checked_add(add)))
+    }
+
+    /// Converts a map of the node and edge each node was reached from, starting at `src`, into a map
+    /// from each node to the first node and edge on the path from `src` to it.
+    #[allow(clippy::type_complexity)]
+    fn first_hops<'a>(
+        src: &'a N,
+        via: &HashMap<&'a N, (&'a N, &'a Option<E>)>,
+    ) -> HashMap<&'a N, (&'a N, &'a Option<E>)> {
+        let mut first: HashMap<&N, (&N, &Option<E>)> = HashMap::new();
+
+        for dst in via.keys() {
+            let mut chain = Vec::new();
+            let mut curr = *dst;
+
+            let hop = loop {
+                if let Some(hop) = first.get(curr) {
+                    break *hop;
+                }
+
+                let (prev, e) = via[curr];
+
+                if prev == src {
+                    break (curr, e);
+                }
+
+                chain.push(curr);
+                curr = prev;
+            };
+
+            first.insert(curr, hop);
+            for n in chain {
+                first.insert(n, hop);
+            }
+        }
+
+        first
     }
 }
@@ -0,0 +1,489 @@
+use super::measure::Measure;
+use super::Graph;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord,
+{
+    /// This function finds the betweenness centrality of every node using Brandes' algorithm, in `O(V * E)`
+    /// time. The betweenness of a node is the sum, over every ordered pair of other nodes `(s, t)`, of the
+    /// fraction of shortest paths from `s` to `t` that pass through it. Every edge has a length of one,
+    /// parallel edges count as a single edge, and loops are ignored.
+    ///
+    /// Edges are followed in their direction, so in a graph built with `add_undirected_edge` each pair is
+    /// counted in both directions, which is twice the usual undirected betweenness. If `normalized` is `true`,
+    /// the values are divided by `(V - 1) * (V - 2)`, the number of ordered pairs not including the node,
+    /// which gives the same result for directed and undirected graphs.
+    ///
+    /// The function returns a `HashMap<&N, f64>` mapping each node to its betweenness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Auth", "Gateway", "Orders", "Billing");
+    /// g.add_edge(&"Auth", &"Gateway", None);
+    /// g.add_edge(&"Gateway", &"Orders", None);
+    /// g.add_edge(&"Gateway", &"Billing", None);
+    /// g.add_edge(&"Orders", &"Billing", None);
+    ///
+    /// let betweenness = g.betweenness_centrality(false);
+    ///
+    /// assert_eq!(betweenness[&"Gateway"], 2.0);
+    /// assert_eq!(betweenness[&"Orders"], 0.0);
+    /// assert_eq!(g.betweenness_centrality(true)[&"Gateway"], 1.0 / 3.0);
+    /// ```
+    pub fn betweenness_centrality(&self, normalized: bool) -> HashMap<&N, f64> {
+        let (nodes, successors) = self.indexed_successors();
+        let n = nodes.len();
+        let mut betweenness = vec![0.0; n];
+
+        for s in 0..n {
+            let mut order = Vec::new();
+            let mut pred: Vec<Vec<usize>> = vec![Vec::new(); n];
+            let mut paths = vec![0.0; n];
+            let mut dist: Vec<Option<usize>> = vec![None; n];
+            let mut queue = VecDeque::from([s]);
+
+            paths[s] = 1.0;
+            dist[s] = Some(0);
+
+            while let Some(u) = queue.pop_front() {
+                order.push(u);
+
+                for &v in successors[u].iter() {
+                    let d = dist[u].map(|d| d + 1);
+
+                    if dist[v].is_none() {
+                        dist[v] = d;
+                        queue.push_back(v);
+                    }
+
+                    if dist[v] == d {
+                        paths[v] += paths[u];
+                        pred[v].push(u);
+                    }
+                }
+            }
+
+            Self::accumulate(s, order, &pred, &paths, &mut betweenness);
+        }
+
+        Self::betweenness_map(nodes, betweenness, normalized)
+    }
+
+    /// This function finds the closeness centrality of every node, i.e. how close it is to the nodes it can
+    /// reach along outgoing edges. Every edge has a length of one.
+    ///
+    /// If a node can reach `r` other nodes with a total distance of `d`, its closeness is `r / d`, scaled by
+    /// `r / (V - 1)` so that nodes reaching only a few others aren't favoured, which is the Wasserman and Faust
+    /// formula. A node that can't reach any others has a closeness of `0`.
+    ///
+    /// The function returns a `HashMap<&N, f64>` mapping each node to its closeness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4);
+    /// g.add_undirected_edge(&1, &2, None);
+    /// g.add_undirected_edge(&2, &3, None);
+    /// g.add_undirected_edge(&3, &4, None);
+    ///
+    /// let closeness = g.closeness_centrality();
+    ///
+    /// assert_eq!(closeness[&1], 0.5);
+    /// assert_eq!(closeness[&2], 0.75);
+    /// ```
+    pub fn closeness_centrality(&self) -> HashMap<&N, f64> {
+        let (nodes, successors) = self.indexed_successors();
+        let n = nodes.len();
+
+        let closeness = (0..n).map(|u| {
+            let dist = Self::bfs_distances(&successors, u);
+            let reached = dist.iter().flatten().filter(|d| **d > 0).count() as f64;
+            let total = dist.iter().flatten().sum::<usize>() as f64;
+
+            if total > 0.0 {
+                (reached / total) * (reached / (n - 1) as f64)
+            } else {
+                0.0
+            }
+        });
+
+        nodes.iter().copied().zip(closeness).collect()
+    }
+
+    /// This function finds the harmonic centrality of every node, which is the sum of `1 / d` over the other
+    /// nodes, where `d` is their distance along outgoing edges. Nodes that can't be reached add nothing, so
+    /// unlike `closeness_centrality` it works naturally on disconnected graphs. Every edge has a length of one.
+    ///
+    /// The function returns a `HashMap<&N, f64>` mapping each node to its harmonic centrality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4);
+    /// g.add_edge(&1, &2, None);
+    /// g.add_edge(&2, &3, None);
+    /// g.add_edge(&1, &4, None);
+    ///
+    /// let harmonic = g.harmonic_centrality();
+    ///
+    /// assert_eq!(harmonic[&1], 2.5);
+    /// assert_eq!(harmonic[&2], 1.0);
+    /// assert_eq!(harmonic[&3], 0.0);
+    /// ```
+    pub fn harmonic_centrality(&self) -> HashMap<&N, f64> {
+        let (nodes, successors) = self.indexed_successors();
+
+        let harmonic = (0..nodes.len()).map(|u| {
+            Self::bfs_distances(&successors, u)
+                .into_iter()
+                .flatten()
+                .filter(|d| *d > 0)
+                .map(|d| 1.0 / d as f64)
+                .sum()
+        });
+
+        nodes.iter().copied().zip(harmonic).collect()
+    }
+
+    /// This function finds the eigenvector centrality of every node using power iteration, where a node is
+    /// important if the nodes with edges to it are important. Each parallel edge counts separately.
+    ///
+    /// Each iteration adds the scores of a node's in-neighbours to its own score, which has the same result
+    /// as the usual definition, but also converges on bipartite graphs. The scores are scaled so that their
+    /// Euclidean norm is `1`, and are updated until the total change across all nodes in one iteration is
+    /// below `tolerance`, or `max_iter` iterations have been run.
+    ///
+    /// The function returns a `HashMap<&N, f64>` mapping each node to its score.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Hub", "A", "B", "C");
+    ///
+    /// for leaf in ["A", "B", "C"] {
+    ///     g.add_undirected_edge(&"Hub", &leaf, None);
+    /// }
+    ///
+    /// let scores = g.eigenvector_centrality(1e-12, 1000);
+    ///
+    /// assert!((scores[&"Hub"] - 0.5_f64.sqrt()).abs() < 1e-6);
+    /// assert!((scores[&"A"] - (1.0_f64 / 6.0).sqrt()).abs() < 1e-6);
+    /// ```
+    pub fn eigenvector_centrality(&self, tolerance: f64, max_iter: usize) -> HashMap<&N, f64> {
+        let (nodes, targets) = self.indexed_targets();
+        let n = nodes.len();
+        let mut score = vec![1.0 / (n as f64).sqrt(); n];
+
+        for _ in 0..max_iter {
+            let mut next = score.clone();
+
+            for (u, edges) in targets.iter().enumerate() {
+                for v in edges.iter() {
+                    next[*v] += score[u];
+                }
+            }
+
+            let norm = next.iter().map(|x| x * x).sum::<f64>().sqrt();
+            next.iter_mut().for_each(|x| *x /= norm);
+
+            let change: f64 = score
+                .iter()
+                .zip(next.iter())
+                .map(|(a, b)| (a - b).abs())
+                .sum();
+            score = next;
+
+            if change < tolerance {
+                break;
+            }
+        }
+
+        nodes.into_iter().zip(score).collect()
+    }
+
+    /// This function finds the hub and authority scores of every node using the HITS algorithm. A good hub
+    /// has edges to good authorities, and a good authority has edges from good hubs. Each parallel edge
+    /// counts separately.
+    ///
+    /// Both sets of scores are scaled so that they add up to `1`, and are updated until the total change across
+    /// all nodes in one iteration is below `tolerance`, or `max_iter` iterations have been run. If the graph
+    /// has no edges, every score is `0`.
+    ///
+    /// The function returns a tuple `(hubs, authorities)`, each of type `HashMap<&N, f64>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Index", "Links", "Rust", "Cargo");
+    /// g.add_edge(&"Index", &"Rust", None);
+    /// g.add_edge(&"Index", &"Cargo", None);
+    /// g.add_edge(&"Links", &"Rust", None);
+    ///
+    /// let (hubs, authorities) = g.hits(1e-12, 1000);
+    ///
+    /// assert!(hubs[&"Index"] > hubs[&"Links"]);
+    /// assert!(authorities[&"Rust"] > authorities[&"Cargo"]);
+    /// assert_eq!(hubs[&"Rust"], 0.0);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn hits(&self, tolerance: f64, max_iter: usize) -> (HashMap<&N, f64>, HashMap<&N, f64>) {
+        let (nodes, targets) = self.indexed_targets();
+        let n = nodes.len();
+        let mut hubs = vec![1.0 / n as f64; n];
+        let mut authorities = vec![0.0; n];
+
+        let scale = |scores: &mut Vec<f64>| {
+            let total: f64 = scores.iter().sum();
+
+            if total > 0.0 {
+                scores.iter_mut().for_each(|x| *x /= total);
+            }
+        };
+
+        for _ in 0..max_iter {
+            let mut next_authorities = vec![0.0; n];
+
+            for (u, edges) in targets.iter().enumerate() {
+                for v in edges.iter() {
+                    next_authorities[*v] += hubs[u];
+                }
+            }
+
+            scale(&mut next_authorities);
+
+            let mut next_hubs: Vec<f64> = targets
+                .iter()
+                .map(|edges| edges.iter().map(|v| next_authorities[*v]).sum())
+                .collect();
+
+            scale(&mut next_hubs);
+
+            let change: f64 = hubs
+                .iter()
+                .zip(next_hubs.iter())
+                .chain(authorities.iter().zip(next_authorities.iter()))
+                .map(|(a, b)| (a - b).abs())
+                .sum();
+
+            hubs = next_hubs;
+            authorities = next_authorities;
+
+            if change < tolerance {
+                break;
+            }
+        }
+
+        (
+            nodes.iter().copied().zip(hubs).collect(),
+            nodes.iter().copied().zip(authorities).collect(),
+        )
+    }
+
+    /// Returns the nodes in order, along with the positions of each node's successors. Parallel edges are
+    /// merged, and loops are left out.
+    fn indexed_successors(&self) -> (Vec<&N>, Vec<Vec<usize>>) {
+        let (nodes, targets) = self.indexed_targets();
+
+        let successors = targets
+            .into_iter()
+            .enumerate()
+            .map(|(u, edges)| {
+                edges
+                    .into_iter()
+                    .filter(|v| *v != u)
+                    .collect::<BTreeSet<usize>>()
+                    .into_iter()
+                    .collect()
+            })
+            .collect();
+
+        (nodes, successors)
+    }
+
+    /// Returns the nodes in order, along with the position of the destination of each node's edges.
+    fn indexed_targets(&self) -> (Vec<&N>, Vec<Vec<usize>>) {
+        let nodes: Vec<&N> = self.nodes.iter().map(|n| &**n).collect();
+        let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+
+        let targets = self
+            .edges
+            .values()
+            .map(|set| set.iter().map(|(dst, _)| index[&**dst]).collect())
+            .collect();
+
+        (nodes, targets)
+    }
+
+    /// Finds the number of edges on a shortest path from `s` to every node, or `None` if it can't be reached.
+    fn bfs_distances(successors: &[Vec<usize>], s: usize) -> Vec<Option<usize>> {
+        let mut dist = vec![None; successors.len()];
+        let mut queue = VecDeque::from([s]);
+        dist[s] = Some(0);
+
+        while let Some(u) = queue.pop_front() {
+            for &v in successors[u].iter() {
+                if dist[v].is_none() {
+                    dist[v] = dist[u].map(|d| d + 1);
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        dist
+    }
+
+    /// Adds the dependencies of `s` on every other node to `betweenness`, visiting the nodes in the reverse
+    /// of the `order` they were reached from `s`. `paths[v]` is the number of shortest paths from `s` to `v`,
+    /// and `pred[v]` holds the nodes before `v` on those paths.
+    fn accumulate(
+        s: usize,
+        order: Vec<usize>,
+        pred: &[Vec<usize>],
+        paths: &[f64],
+        betweenness: &mut [f64],
+    ) {
+        let mut dependency = vec![0.0; paths.len()];
+
+        for w in order.into_iter().rev() {
+            for &v in pred[w].iter() {
+                dependency[v] += paths[v] / paths[w] * (1.0 + dependency[w]);
+            }
+
+            if w != s {
+                betweenness[w] += dependency[w];
+            }
+        }
+    }
+
+    fn betweenness_map(
+        nodes: Vec<&N>,
+        mut betweenness: Vec<f64>,
+        normalized: bool,
+    ) -> HashMap<&N, f64> {
+        let n = nodes.len();
+
+        if normalized && n > 2 {
+            let pairs = ((n - 1) * (n - 2)) as f64;
+            betweenness.iter_mut().for_each(|b| *b /= pairs);
+        }
+
+        nodes.into_iter().zip(betweenness).collect()
+    }
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord + Measure,
+{
+    /// This function finds the betweenness centrality of every node in the same way as
+    /// `betweenness_centrality`, except that the length of a path is the total weight of its edges, using
+    /// Brandes' algorithm with Djikstra's algorithm in `O(V * E * log(V))` time. Unweighted edges have a
+    /// weight of `Measure::default_weight`, and the lightest of several parallel edges is used. Weights
+    /// should be positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, u32> = graph_with_nodes!("Sydney", "Canberra", "Melbourne");
+    /// g.add_undirected_edge(&"Sydney", &"Canberra", Some(3));
+    /// g.add_undirected_edge(&"Canberra", &"Melbourne", Some(6));
+    /// g.add_undirected_edge(&"Sydney", &"Melbourne", Some(10));
+    ///
+    /// let betweenness = g.weighted_betweenness_centrality(false);
+    ///
+    /// assert_eq!(betweenness[&"Canberra"], 2.0);
+    /// assert_eq!(g.betweenness_centrality(false)[&"Canberra"], 0.0);
+    /// ```
+    pub fn weighted_betweenness_centrality(&self, normalized: bool) -> HashMap<&N, f64> {
+        let nodes: Vec<&N> = self.nodes.iter().map(|n| &**n).collect();
+        let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let n = nodes.len();
+
+        let successors: Vec<Vec<(usize, E)>> = self
+            .edges
+            .iter()
+            .map(|(src, set)| {
+                let mut lightest: HashMap<usize, E> = HashMap::new();
+
+                for (dst, e) in set.iter().filter(|(dst, _)| dst != src) {
+                    let w = e.clone().unwrap_or_else(E::default_weight);
+
+                    lightest
+                        .entry(index[&**dst])
+                        .and_modify(|curr| *curr = curr.clone().min(w.clone()))
+                        .or_insert(w);
+                }
+
+                let mut lightest: Vec<(usize, E)> = lightest.into_iter().collect();
+                lightest.sort();
+
+                lightest
+            })
+            .collect();
+
+        let mut betweenness = vec![0.0; n];
+
+        for s in 0..n {
+            let mut order = Vec::new();
+            let mut pred: Vec<Vec<usize>> = vec![Vec::new(); n];
+            let mut paths = vec![0.0; n];
+            let mut dist: Vec<Option<E>> = vec![None; n];
+            let mut done = vec![false; n];
+            let mut pq = BinaryHeap::from([Reverse((E::zero(), s))]);
+
+            paths[s] = 1.0;
+            dist[s] = Some(E::zero());
+
+            while let Some(Reverse((d, u))) = pq.pop() {
+                if done[u] {
+                    continue;
+                }
+
+                done[u] = true;
+                order.push(u);
+
+                for (v, w) in successors[u].iter() {
+                    let next = d.saturating_add(w);
+
+                    match &dist[*v] {
+                        Some(curr) if next > *curr => continue,
+                        Some(curr) if next == *curr => {}
+                        _ => {
+                            dist[*v] = Some(next.clone());
+                            paths[*v] = 0.0;
+                            pred[*v].clear();
+                            pq.push(Reverse((next, *v)));
+                        }
+                    }
+
+                    paths[*v] += paths[u];
+                    pred[*v].push(u);
+                }
+            }
+
+            Self::accumulate(s, order, &pred, &paths, &mut betweenness);
+        }
+
+        Self::betweenness_map(nodes, betweenness, normalized)
+    }
+}
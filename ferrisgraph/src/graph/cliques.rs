@@ -0,0 +1,191 @@
+use super::Graph;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// An iterator over the maximal cliques of a graph, returned by `maximal_cliques`.
+///
+/// Cliques are found lazily, so stopping early skips the rest of the search.
+pub struct MaximalCliques<'a, N> {
+    adjacent: HashMap<&'a N, BTreeSet<&'a N>>,
+    stack: Vec<Frame<'a, N>>,
+}
+
+/// A call of the Bron-Kerbosch algorithm, where `clique` is the clique being grown, `candidates` are the
+/// nodes that could still be added to it, and `excluded` are the nodes whose cliques were already found.
+/// `branches` holds the candidates left to try, which are the ones not adjacent to the pivot.
+struct Frame<'a, N> {
+    clique: BTreeSet<&'a N>,
+    candidates: BTreeSet<&'a N>,
+    excluded: BTreeSet<&'a N>,
+    branches: Vec<&'a N>,
+}
+
+impl<'a, N> MaximalCliques<'a, N>
+where
+    N: Hash + Eq + Ord,
+{
+    /// Builds the call that grows `clique`, picking the pivot with the most neighbours among `candidates`.
+    fn frame(
+        &self,
+        clique: BTreeSet<&'a N>,
+        candidates: BTreeSet<&'a N>,
+        excluded: BTreeSet<&'a N>,
+    ) -> Frame<'a, N> {
+        let pivot = candidates
+            .iter()
+            .chain(excluded.iter())
+            .max_by_key(|u| self.adjacent[*u].intersection(&candidates).count());
+
+        let branches = match pivot {
+            Some(pivot) => candidates
+                .iter()
+                .rev()
+                .filter(|v| !self.adjacent[pivot].contains(*v))
+                .copied()
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Frame {
+            clique,
+            candidates,
+            excluded,
+            branches,
+        }
+    }
+
+    /// Finds the next maximal clique with more than `bound` nodes, skipping any branch of the search that
+    /// can't reach that size.
+    fn next_larger(&mut self, bound: usize) -> Option<BTreeSet<&'a N>> {
+        while let Some(frame) = self.stack.last_mut() {
+            let Some(v) = frame.branches.pop() else {
+                self.stack.pop();
+                continue;
+            };
+
+            let neighbours = &self.adjacent[v];
+            let mut clique = frame.clique.clone();
+            clique.insert(v);
+
+            let candidates: BTreeSet<&N> =
+                frame.candidates.intersection(neighbours).copied().collect();
+            let excluded: BTreeSet<&N> = frame.excluded.intersection(neighbours).copied().collect();
+
+            frame.candidates.remove(v);
+            frame.excluded.insert(v);
+
+            if clique.len() + candidates.len() <= bound {
+                continue;
+            }
+
+            if candidates.is_empty() {
+                if excluded.is_empty() {
+                    return Some(clique);
+                }
+
+                continue;
+            }
+
+            let next = self.frame(clique, candidates, excluded);
+            self.stack.push(next);
+        }
+
+        None
+    }
+}
+
+impl<'a, N> Iterator for MaximalCliques<'a, N>
+where
+    N: Hash + Eq + Ord,
+{
+    type Item = BTreeSet<&'a N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_larger(0)
+    }
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord,
+{
+    /// This function returns an iterator over the maximal cliques of the graph, i.e. the sets of nodes that
+    /// are all connected to each other and can't be extended by another node. It uses the Bron-Kerbosch
+    /// algorithm with pivoting, which takes `O(3^(V/3))` time in the worst case, but is fast on sparse graphs.
+    ///
+    /// The direction of edges is ignored, and loops are ignored, so a node without any other edges is a
+    /// clique on its own. Each clique is returned once, as a `BTreeSet<&N>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Ann", "Bob", "Cat", "Dan", "Eve");
+    /// g.add_edge(&"Ann", &"Bob", None);
+    /// g.add_edge(&"Bob", &"Cat", None);
+    /// g.add_edge(&"Cat", &"Ann", None);
+    /// g.add_edge(&"Cat", &"Dan", None);
+    ///
+    /// let mut cliques: Vec<BTreeSet<&&str>> = g.maximal_cliques().collect();
+    /// cliques.sort();
+    ///
+    /// assert_eq!(
+    ///     cliques,
+    ///     vec![
+    ///         BTreeSet::from([&"Ann", &"Bob", &"Cat"]),
+    ///         BTreeSet::from([&"Cat", &"Dan"]),
+    ///         BTreeSet::from([&"Eve"]),
+    ///     ]
+    /// );
+    /// ```
+    pub fn maximal_cliques(&self) -> MaximalCliques<'_, N> {
+        let mut cliques = MaximalCliques {
+            adjacent: self.undirected_adjacency(),
+            stack: Vec::new(),
+        };
+
+        let root = cliques.frame(
+            BTreeSet::new(),
+            self.nodes.iter().map(|n| &**n).collect(),
+            BTreeSet::new(),
+        );
+        cliques.stack.push(root);
+
+        cliques
+    }
+
+    /// This function finds a maximum clique of the graph, i.e. a largest set of nodes that are all connected
+    /// to each other. The search is the same as in `maximal_cliques`, but skips any branch that can't beat the
+    /// largest clique found so far. The direction of edges is ignored, and loops are ignored.
+    ///
+    /// If there are several maximum cliques, one of them is returned. The clique is empty if the graph is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4, 5);
+    ///
+    /// for (u, v) in [(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4), (4, 5)] {
+    ///     g.add_undirected_edge(&u, &v, None);
+    /// }
+    ///
+    /// assert_eq!(g.maximum_clique(), BTreeSet::from([&1, &2, &3, &4]));
+    /// ```
+    pub fn maximum_clique(&self) -> BTreeSet<&N> {
+        let mut cliques = self.maximal_cliques();
+        let mut best = BTreeSet::new();
+
+        while let Some(clique) = cliques.next_larger(best.len()) {
+            best = clique;
+        }
+
+        best
+    }
+}
@@ -0,0 +1,368 @@
+use super::Graph;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// The triangles through each node, along with the degrees needed to turn them into clustering coefficients,
+/// all indexed by the position of the node.
+struct Triangles<'a, N> {
+    nodes: Vec<&'a N>,
+    /// The number of triangles through each node, ignoring the direction of edges.
+    undirected: Vec<usize>,
+    /// The number of directed triangles through each node, as defined in `directed_triangle_count`.
+    directed: Vec<usize>,
+    /// The number of neighbours of each node, ignoring the direction of edges.
+    degree: Vec<usize>,
+    /// The in-degree plus the out-degree of each node.
+    total_degree: Vec<usize>,
+    /// The number of neighbours of each node with edges in both directions.
+    reciprocal: Vec<usize>,
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord,
+{
+    /// This function counts the triangles in the graph, i.e. the sets of three nodes that are all connected
+    /// to each other. The graph is treated as undirected, so an edge in either direction connects two nodes,
+    /// parallel edges count as a single edge, and loops are ignored.
+    ///
+    /// Triangles are listed by only following edges from each node to neighbours of higher degree, which takes
+    /// `O(E * sqrt(E))` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4);
+    /// g.add_undirected_edge(&1, &2, None);
+    /// g.add_undirected_edge(&2, &3, None);
+    /// g.add_edge(&3, &1, None);
+    /// g.add_edge(&1, &4, None);
+    /// g.add_edge(&4, &3, None);
+    ///
+    /// assert_eq!(g.triangle_count(), 2);
+    /// ```
+    pub fn triangle_count(&self) -> usize {
+        self.triangles().undirected.iter().sum::<usize>() / 3
+    }
+
+    /// This function counts the directed triangles in the graph, i.e. the ways of choosing one directed edge
+    /// between each pair of three nodes, whichever way the edges go. A triangle with edges in both directions
+    /// between every pair counts as eight directed triangles, and a triangle with a single edge between
+    /// each pair counts as one. Parallel edges count as a single edge, and loops are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3);
+    /// g.add_edge(&1, &2, None);
+    /// g.add_edge(&2, &3, None);
+    /// g.add_edge(&3, &1, None);
+    ///
+    /// assert_eq!(g.directed_triangle_count(), 1);
+    ///
+    /// g.add_edge(&2, &1, None);
+    /// assert_eq!(g.directed_triangle_count(), 2);
+    /// ```
+    pub fn directed_triangle_count(&self) -> usize {
+        self.triangles().directed.iter().sum::<usize>() / 3
+    }
+
+    /// This function finds the local clustering coefficient of every node, i.e. the fraction of pairs of its
+    /// neighbours that are connected to each other. Nodes with fewer than two neighbours have a coefficient
+    /// of `0`. The graph is treated as undirected, in the same way as `triangle_count`.
+    ///
+    /// The function returns a `HashMap<&N, f64>` mapping each node to its coefficient.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Ann", "Bob", "Cat", "Dan");
+    /// g.add_undirected_edge(&"Ann", &"Bob", None);
+    /// g.add_undirected_edge(&"Ann", &"Cat", None);
+    /// g.add_undirected_edge(&"Ann", &"Dan", None);
+    /// g.add_undirected_edge(&"Bob", &"Cat", None);
+    ///
+    /// let clustering = g.local_clustering();
+    ///
+    /// assert_eq!(clustering[&"Ann"], 1.0 / 3.0);
+    /// assert_eq!(clustering[&"Bob"], 1.0);
+    /// assert_eq!(clustering[&"Dan"], 0.0);
+    /// ```
+    pub fn local_clustering(&self) -> HashMap<&N, f64> {
+        let t = self.triangles();
+
+        t.nodes
+            .iter()
+            .copied()
+            .zip(Self::coefficients(&t))
+            .collect()
+    }
+
+    /// This function finds the directed local clustering coefficient of every node, as defined by Fagiolo,
+    /// which is the number of directed triangles through the node, as in `directed_triangle_count`, divided
+    /// by the number there would be if all of its neighbours were connected in both directions.
+    ///
+    /// If a node has an in-degree plus out-degree of `d`, and `b` neighbours connected to it in both
+    /// directions, its coefficient is `t / (d * (d - 1) - 2 * b)`, where `t` is its number of directed
+    /// triangles. Nodes that can't be in any triangle have a coefficient of `0`. Parallel edges count as a
+    /// single edge, and loops are ignored.
+    ///
+    /// The function returns a `HashMap<&N, f64>` mapping each node to its coefficient.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3);
+    /// g.add_edge(&1, &2, None);
+    /// g.add_edge(&2, &3, None);
+    /// g.add_edge(&3, &1, None);
+    ///
+    /// assert_eq!(g.directed_local_clustering()[&1], 0.5);
+    ///
+    /// g.add_edge(&2, &1, None);
+    /// g.add_edge(&3, &2, None);
+    /// g.add_edge(&1, &3, None);
+    ///
+    /// assert_eq!(g.directed_local_clustering()[&1], 1.0);
+    /// ```
+    pub fn directed_local_clustering(&self) -> HashMap<&N, f64> {
+        let t = self.triangles();
+
+        t.nodes
+            .iter()
+            .copied()
+            .zip(Self::directed_coefficients(&t))
+            .collect()
+    }
+
+    /// This function finds the average of the local clustering coefficients of every node, as given by
+    /// `local_clustering`. Nodes with fewer than two neighbours are included with a coefficient of `0`, and
+    /// a graph without any nodes has an average of `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Ann", "Bob", "Cat", "Dan");
+    /// g.add_undirected_edge(&"Ann", &"Bob", None);
+    /// g.add_undirected_edge(&"Ann", &"Cat", None);
+    /// g.add_undirected_edge(&"Ann", &"Dan", None);
+    /// g.add_undirected_edge(&"Bob", &"Cat", None);
+    ///
+    /// assert!((g.average_clustering() - 7.0 / 12.0).abs() < 1e-12);
+    /// ```
+    pub fn average_clustering(&self) -> f64 {
+        Self::average(Self::coefficients(&self.triangles()))
+    }
+
+    /// This function finds the average of the directed local clustering coefficients of every node, as given
+    /// by `directed_local_clustering`. A graph without any nodes has an average of `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3);
+    /// g.add_edge(&1, &2, None);
+    /// g.add_edge(&2, &3, None);
+    /// g.add_edge(&3, &1, None);
+    ///
+    /// assert_eq!(g.directed_average_clustering(), 0.5);
+    /// ```
+    pub fn directed_average_clustering(&self) -> f64 {
+        Self::average(Self::directed_coefficients(&self.triangles()))
+    }
+
+    /// This function finds the transitivity of the graph, i.e. the fraction of paths of length two that are
+    /// closed into a triangle, which is three times the number of triangles divided by the number of such
+    /// paths. Unlike `average_clustering`, this gives more weight to nodes with many neighbours. The graph is
+    /// treated as undirected, in the same way as `triangle_count`, and a graph without any paths of length
+    /// two has a transitivity of `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Ann", "Bob", "Cat", "Dan");
+    /// g.add_undirected_edge(&"Ann", &"Bob", None);
+    /// g.add_undirected_edge(&"Ann", &"Cat", None);
+    /// g.add_undirected_edge(&"Ann", &"Dan", None);
+    /// g.add_undirected_edge(&"Bob", &"Cat", None);
+    ///
+    /// assert_eq!(g.transitivity(), 0.6);
+    /// ```
+    pub fn transitivity(&self) -> f64 {
+        let t = self.triangles();
+        let paths = t.degree.iter().map(|d| d * d.saturating_sub(1)).sum();
+
+        Self::ratio(2 * t.undirected.iter().sum::<usize>(), paths)
+    }
+
+    /// This function finds the directed transitivity of the graph, which is the total number of directed
+    /// triangles through each node divided by the total number there could be, using the same counts as
+    /// `directed_local_clustering`. A graph that can't have any triangles has a transitivity of `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4);
+    /// g.add_edge(&1, &2, None);
+    /// g.add_edge(&2, &3, None);
+    /// g.add_edge(&3, &1, None);
+    /// g.add_edge(&3, &4, None);
+    ///
+    /// assert_eq!(g.directed_transitivity(), 0.3);
+    /// ```
+    pub fn directed_transitivity(&self) -> f64 {
+        let t = self.triangles();
+        let pairs = (0..t.nodes.len())
+            .map(|u| Self::directed_pairs(&t, u))
+            .sum();
+
+        Self::ratio(t.directed.iter().sum(), pairs)
+    }
+
+    /// Lists every triangle of the undirected graph once, by ranking the nodes by degree and only following
+    /// edges from lower to higher ranked nodes. Each triangle adds one undirected triangle to each of its
+    /// nodes, and as many directed triangles as there are ways to choose its edges.
+    fn triangles(&self) -> Triangles<'_, N> {
+        let nodes: Vec<&N> = self.nodes.iter().map(|n| &**n).collect();
+        let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let n = nodes.len();
+
+        let successors: Vec<BTreeSet<usize>> = self
+            .edges
+            .iter()
+            .map(|(src, set)| {
+                set.iter()
+                    .filter(|(dst, _)| dst != src)
+                    .map(|(dst, _)| index[&**dst])
+                    .collect()
+            })
+            .collect();
+
+        let mut neighbours: Vec<BTreeSet<usize>> = successors.clone();
+        let mut total_degree: Vec<usize> = successors.iter().map(|s| s.len()).collect();
+        let mut reciprocal = vec![0; n];
+
+        for (u, targets) in successors.iter().enumerate() {
+            for &v in targets.iter() {
+                neighbours[v].insert(u);
+                total_degree[v] += 1;
+
+                if successors[v].contains(&u) {
+                    reciprocal[u] += 1;
+                }
+            }
+        }
+
+        // The number of directed edges between two neighbours, which is either one or two.
+        let edges_between = |u: usize, v: usize| {
+            usize::from(successors[u].contains(&v)) + usize::from(successors[v].contains(&u))
+        };
+
+        let degree: Vec<usize> = neighbours.iter().map(|s| s.len()).collect();
+        let higher: Vec<Vec<usize>> = neighbours
+            .iter()
+            .enumerate()
+            .map(|(u, set)| {
+                set.iter()
+                    .copied()
+                    .filter(|v| (degree[*v], *v) > (degree[u], u))
+                    .collect()
+            })
+            .collect();
+
+        let mut undirected = vec![0; n];
+        let mut directed = vec![0; n];
+        let mut marked = vec![false; n];
+
+        for u in 0..n {
+            for &v in higher[u].iter() {
+                marked[v] = true;
+            }
+
+            for &v in higher[u].iter() {
+                for &w in higher[v].iter().filter(|w| marked[**w]) {
+                    let ways = edges_between(u, v) * edges_between(v, w) * edges_between(w, u);
+
+                    for x in [u, v, w] {
+                        undirected[x] += 1;
+                        directed[x] += ways;
+                    }
+                }
+            }
+
+            for &v in higher[u].iter() {
+                marked[v] = false;
+            }
+        }
+
+        Triangles {
+            nodes,
+            undirected,
+            directed,
+            degree,
+            total_degree,
+            reciprocal,
+        }
+    }
+
+    /// The local clustering coefficient of each node, in the order of the nodes.
+    fn coefficients(t: &Triangles<'_, N>) -> Vec<f64> {
+        (0..t.nodes.len())
+            .map(|u| {
+                Self::ratio(
+                    2 * t.undirected[u],
+                    t.degree[u] * t.degree[u].saturating_sub(1),
+                )
+            })
+            .collect()
+    }
+
+    /// The directed local clustering coefficient of each node, in the order of the nodes.
+    fn directed_coefficients(t: &Triangles<'_, N>) -> Vec<f64> {
+        (0..t.nodes.len())
+            .map(|u| Self::ratio(t.directed[u], Self::directed_pairs(t, u)))
+            .collect()
+    }
+
+    /// The number of directed triangles there could be through the node at position `u`.
+    fn directed_pairs(t: &Triangles<'_, N>, u: usize) -> usize {
+        let d = t.total_degree[u];
+
+        d * d.saturating_sub(1) - 2 * t.reciprocal[u]
+    }
+
+    /// Divides `a` by `b`, or returns `0` if `b` is `0`.
+    fn ratio(a: usize, b: usize) -> f64 {
+        if b == 0 {
+            0.0
+        } else {
+            a as f64 / b as f64
+        }
+    }
+
+    fn average(values: Vec<f64>) -> f64 {
+        if values.is_empty() {
+            0.0
+        } else {
+            values.iter().sum::<f64>() / values.len() as f64
+        }
+    }
+}
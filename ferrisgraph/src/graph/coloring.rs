@@ -0,0 +1,239 @@
+use super::graph::GraphError;
+use super::Graph;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord,
+{
+    /// This function colors the nodes of the graph so that no two adjacent nodes share a color, visiting
+    /// the nodes in the given order and giving each one the smallest color not used by its neighbours.
+    /// Nodes missing from `order` are colored afterwards, in sorted order. The direction of edges is ignored,
+    /// and loops are ignored.
+    ///
+    /// The function returns a `HashMap<&N, usize>` mapping each node to its color, where colors count up
+    /// from `0`. `GraphError::NodeNotFound` is returned if a node in `order` doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Maths", "Physics", "History", "Art");
+    /// g.add_undirected_edge(&"Maths", &"Physics", None);
+    /// g.add_undirected_edge(&"Physics", &"History", None);
+    /// g.add_edge(&"History", &"Art", None);
+    ///
+    /// let colors = g.greedy_coloring(&[&"Physics", &"Maths", &"History"]).unwrap();
+    ///
+    /// assert_eq!(colors[&"Physics"], 0);
+    /// assert_eq!(colors[&"Maths"], 1);
+    /// assert_eq!(colors[&"History"], 1);
+    /// assert_eq!(colors[&"Art"], 0);
+    /// ```
+    pub fn greedy_coloring<'a>(
+        &'a self,
+        order: &[&'a N],
+    ) -> Result<HashMap<&'a N, usize>, GraphError<'a, N>> {
+        let (nodes, adjacent) = self.indexed_adjacency();
+        let mut colors: Vec<Option<usize>> = vec![None; nodes.len()];
+        let mut sequence = Vec::new();
+
+        for n in order.iter() {
+            match nodes.binary_search(n) {
+                Ok(u) => sequence.push(u),
+                Err(_) => return Err(GraphError::NodeNotFound(n)),
+            }
+        }
+
+        for u in sequence.into_iter().chain(0..nodes.len()) {
+            if colors[u].is_none() {
+                colors[u] = Some(Self::smallest_free_color(&adjacent[u], &colors));
+            }
+        }
+
+        Ok(Self::color_map(&nodes, &colors))
+    }
+
+    /// This function colors the nodes of the graph so that no two adjacent nodes share a color, using the
+    /// DSATUR heuristic. It repeatedly colors the node whose neighbours already use the most distinct colors,
+    /// breaking ties by degree, and gives it the smallest color not used by its neighbours. It usually uses
+    /// fewer colors than `greedy_coloring`. The direction of edges is ignored, and loops are ignored.
+    ///
+    /// The function returns a `HashMap<&N, usize>` mapping each node to its color, where colors count up
+    /// from `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// // A cycle of even length only needs two colors
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4, 5, 6);
+    /// g.add_undirected_edge(&1, &4, None);
+    /// g.add_undirected_edge(&4, &2, None);
+    /// g.add_undirected_edge(&2, &5, None);
+    /// g.add_undirected_edge(&5, &3, None);
+    /// g.add_undirected_edge(&3, &6, None);
+    /// g.add_undirected_edge(&6, &1, None);
+    ///
+    /// let colors = g.dsatur_coloring();
+    ///
+    /// assert_eq!(colors.values().max(), Some(&1));
+    /// assert_ne!(colors[&1], colors[&4]);
+    /// ```
+    pub fn dsatur_coloring(&self) -> HashMap<&N, usize> {
+        let (nodes, adjacent) = self.indexed_adjacency();
+        let mut colors: Vec<Option<usize>> = vec![None; nodes.len()];
+        let mut saturation: Vec<HashSet<usize>> = vec![HashSet::new(); nodes.len()];
+
+        for _ in 0..nodes.len() {
+            let u = (0..nodes.len())
+                .filter(|u| colors[*u].is_none())
+                .max_by_key(|u| (saturation[*u].len(), adjacent[*u].len(), Reverse(*u)))
+                .expect("A node is left to color");
+
+            let color = Self::smallest_free_color(&adjacent[u], &colors);
+            colors[u] = Some(color);
+
+            for v in adjacent[u].iter() {
+                saturation[*v].insert(color);
+            }
+        }
+
+        Self::color_map(&nodes, &colors)
+    }
+
+    /// This function finds the chromatic number of the graph, i.e. the fewest colors needed so that no two
+    /// adjacent nodes share a color, along with a coloring that uses that many. The direction of edges is
+    /// ignored, and loops are ignored.
+    ///
+    /// It starts from the number of colors used by `dsatur_coloring`, and then searches for colorings with
+    /// fewer colors by backtracking. This takes exponential time in the worst case, so it is only practical
+    /// for small graphs.
+    ///
+    /// The function returns a tuple `(chromatic_number, colors)`, in which `colors` is of type
+    /// `HashMap<&N, usize>`, mapping each node to a color between `0` and `chromatic_number - 1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// // A wheel with an odd number of spokes needs four colors
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(0, 1, 2, 3, 4, 5);
+    ///
+    /// for i in 1..=5 {
+    ///     g.add_undirected_edge(&0, &i, None);
+    ///     g.add_undirected_edge(&i, &(i % 5 + 1), None);
+    /// }
+    ///
+    /// let (chromatic_number, colors) = g.chromatic_number();
+    ///
+    /// assert_eq!(chromatic_number, 4);
+    /// assert_ne!(colors[&0], colors[&3]);
+    /// ```
+    pub fn chromatic_number(&self) -> (usize, HashMap<&N, usize>) {
+        let (nodes, adjacent) = self.indexed_adjacency();
+        let best = self.dsatur_coloring();
+        let mut k = best.values().max().map_or(0, |c| c + 1);
+
+        let mut colors: Vec<Option<usize>> = nodes.iter().map(|n| Some(best[n])).collect();
+        let lower = if adjacent.iter().any(|a| !a.is_empty()) {
+            2
+        } else {
+            nodes.len().min(1)
+        };
+
+        while k > lower {
+            let mut attempt: Vec<Option<usize>> = vec![None; nodes.len()];
+
+            if !Self::color_with(&adjacent, k - 1, &mut attempt) {
+                break;
+            }
+
+            colors = attempt;
+            k -= 1;
+        }
+
+        (k, Self::color_map(&nodes, &colors))
+    }
+
+    /// Tries to color the remaining nodes with at most `k` colors by backtracking, always coloring the node
+    /// with the most distinct colors among its neighbours next. Returns `true` if successful.
+    fn color_with(adjacent: &[Vec<usize>], k: usize, colors: &mut Vec<Option<usize>>) -> bool {
+        let saturation = |u: usize| {
+            adjacent[u]
+                .iter()
+                .filter_map(|v| colors[*v])
+                .collect::<HashSet<usize>>()
+                .len()
+        };
+
+        let Some(u) = (0..adjacent.len())
+            .filter(|u| colors[*u].is_none())
+            .max_by_key(|u| (saturation(*u), adjacent[*u].len(), Reverse(*u)))
+        else {
+            return true;
+        };
+
+        // Colors above the largest one used so far are interchangeable, so only the first of them is tried.
+        let used = colors.iter().flatten().max().map_or(0, |c| c + 1);
+
+        for color in 0..k.min(used + 1) {
+            if adjacent[u].iter().any(|v| colors[*v] == Some(color)) {
+                continue;
+            }
+
+            colors[u] = Some(color);
+
+            if Self::color_with(adjacent, k, colors) {
+                return true;
+            }
+        }
+
+        colors[u] = None;
+
+        false
+    }
+
+    /// Returns the nodes in order, along with the positions of each node's neighbours when the direction of
+    /// edges is ignored.
+    fn indexed_adjacency(&self) -> (Vec<&N>, Vec<Vec<usize>>) {
+        let nodes: Vec<&N> = self.nodes.iter().map(|n| &**n).collect();
+        let undirected = self.undirected_adjacency();
+
+        let adjacent = nodes
+            .iter()
+            .map(|n| {
+                undirected[n]
+                    .iter()
+                    .map(|v| nodes.binary_search(v).expect("Neighbours are nodes"))
+                    .collect()
+            })
+            .collect();
+
+        (nodes, adjacent)
+    }
+
+    /// Returns the smallest color that none of the given neighbours have.
+    fn smallest_free_color(neighbours: &[usize], colors: &[Option<usize>]) -> usize {
+        let used: HashSet<usize> = neighbours.iter().filter_map(|v| colors[*v]).collect();
+
+        (0..)
+            .find(|c| !used.contains(c))
+            .expect("There are infinitely many colors")
+    }
+
+    fn color_map<'a>(nodes: &[&'a N], colors: &[Option<usize>]) -> HashMap<&'a N, usize> {
+        nodes
+            .iter()
+            .zip(colors.iter())
+            .map(|(n, c)| (*n, c.expect("Every node is colored")))
+            .collect()
+    }
+}
@@ -0,0 +1,353 @@
+use super::graph::GraphError;
+use super::Graph;
+use crate::random::Lcg;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// The largest number of rounds that `label_propagation` runs for. Ties are broken at random, so the labels
+/// aren't guaranteed to settle, and the partition reached after this many rounds is returned instead.
+pub const LABEL_PROPAGATION_ROUNDS: usize = 100;
+
+/// A partition of the nodes of a graph into communities, found by `louvain_communities` or
+/// `label_propagation`.
+///
+/// `communities` holds every node exactly once, where the nodes within a community are sorted, and the
+/// communities are sorted. `modularity` is the score of the partition given by `Graph::modularity`.
+#[derive(Debug, PartialEq)]
+pub struct Partition<'a, N> {
+    pub communities: Vec<Vec<&'a N>>,
+    pub modularity: f64,
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord,
+{
+    /// This function finds the modularity of a partition of the graph into communities, which measures how
+    /// many more edges there are within the communities than would be expected if the edges were placed at
+    /// random. It is between `-0.5` and `1`, and higher scores mean more tightly knit communities.
+    ///
+    /// The graph is treated as undirected: the two directed edges added by `add_undirected_edge` count as a
+    /// single edge, parallel edges with different weights count separately, and loops are ignored. Nodes
+    /// missing from `communities` are each put in a community of their own. A graph without any edges has a
+    /// modularity of `0`.
+    ///
+    /// Returned errors are:
+    /// - `GraphError::NodeNotFound` if a node in `communities` doesn't exist.
+    /// - `GraphError::OverlappingCommunities` if a node is in more than one community.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::{Graph, graph::GraphError}, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4);
+    /// g.add_undirected_edge(&1, &2, None);
+    /// g.add_undirected_edge(&3, &4, None);
+    ///
+    /// assert_eq!(g.modularity(&[vec![&1, &2], vec![&3, &4]]).unwrap(), 0.5);
+    /// assert_eq!(g.modularity(&[vec![&1, &2, &3, &4]]).unwrap(), 0.0);
+    /// assert_eq!(
+    ///     g.modularity(&[vec![&1, &2], vec![&2, &3]]).unwrap_err(),
+    ///     GraphError::OverlappingCommunities(&2)
+    /// );
+    /// ```
+    pub fn modularity<'a>(&'a self, communities: &[Vec<&'a N>]) -> Result<f64, GraphError<'a, N>> {
+        let (nodes, adjacent) = self.weighted_undirected();
+        let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+
+        // Nodes that aren't in any of the given communities start off in one of their own.
+        let mut membership: Vec<Option<usize>> = vec![None; nodes.len()];
+
+        for (c, community) in communities.iter().enumerate() {
+            for n in community.iter() {
+                let Some(&u) = index.get(n) else {
+                    return Err(GraphError::NodeNotFound(n));
+                };
+
+                if membership[u].replace(c).is_some() {
+                    return Err(GraphError::OverlappingCommunities(n));
+                }
+            }
+        }
+
+        let membership: Vec<usize> = membership
+            .into_iter()
+            .enumerate()
+            .map(|(u, c)| c.unwrap_or(communities.len() + u))
+            .collect();
+
+        Ok(Self::modularity_of(&adjacent, &membership, 1.0))
+    }
+
+    /// This function finds communities using the Louvain method. Starting with every node in its own
+    /// community, it repeatedly moves single nodes to the neighbouring community that increases modularity
+    /// the most, then merges each community into a single node and starts again, until no move helps.
+    ///
+    /// `resolution` controls the size of the communities, where higher values give more, smaller communities,
+    /// and `1.0` maximises the usual modularity. The graph is treated as undirected, in the same way as
+    /// `modularity`, and the search is deterministic.
+    ///
+    /// The function returns a `Partition`, holding the communities and their modularity, which is always
+    /// found with a resolution of `1.0` so that scores can be compared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Cart", "Checkout", "Payment", "Search", "Index", "Ranking");
+    /// g.add_undirected_edge(&"Cart", &"Checkout", None);
+    /// g.add_undirected_edge(&"Checkout", &"Payment", None);
+    /// g.add_undirected_edge(&"Payment", &"Cart", None);
+    /// g.add_undirected_edge(&"Search", &"Index", None);
+    /// g.add_undirected_edge(&"Index", &"Ranking", None);
+    /// g.add_undirected_edge(&"Ranking", &"Search", None);
+    /// g.add_undirected_edge(&"Cart", &"Search", None);
+    ///
+    /// let partition = g.louvain_communities(1.0);
+    ///
+    /// assert_eq!(
+    ///     partition.communities,
+    ///     vec![vec![&"Cart", &"Checkout", &"Payment"], vec![&"Index", &"Ranking", &"Search"]]
+    /// );
+    /// assert!((partition.modularity - 5.0 / 14.0).abs() < 1e-9);
+    /// ```
+    pub fn louvain_communities(&self, resolution: f64) -> Partition<'_, N> {
+        let (nodes, original) = self.weighted_undirected();
+        let mut membership: Vec<usize> = (0..nodes.len()).collect();
+        let mut adjacent = original.clone();
+
+        loop {
+            let n = adjacent.len();
+            let degree: Vec<f64> = adjacent.iter().map(|a| a.values().sum()).collect();
+            let total: f64 = degree.iter().sum();
+
+            if total == 0.0 {
+                break;
+            }
+
+            // `tot[c]` is the total degree of the nodes in community `c`.
+            let mut community: Vec<usize> = (0..n).collect();
+            let mut tot = degree.clone();
+            let mut moved = false;
+
+            loop {
+                let mut improved = false;
+
+                for u in 0..n {
+                    let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+
+                    for (v, w) in adjacent[u].iter().filter(|(v, _)| **v != u) {
+                        *links.entry(community[*v]).or_default() += w;
+                    }
+
+                    let curr = community[u];
+                    tot[curr] -= degree[u];
+
+                    // The change in modularity from adding `u` to `c`, scaled by the total degree.
+                    let gain = |c: usize| {
+                        links.get(&c).copied().unwrap_or(0.0)
+                            - resolution * tot[c] * degree[u] / total
+                    };
+
+                    let mut best = (gain(curr), curr);
+
+                    for c in links.keys() {
+                        if gain(*c) > best.0 + 1e-12 {
+                            best = (gain(*c), *c);
+                        }
+                    }
+
+                    tot[best.1] += degree[u];
+                    community[u] = best.1;
+
+                    if best.1 != curr {
+                        improved = true;
+                        moved = true;
+                    }
+                }
+
+                if !improved {
+                    break;
+                }
+            }
+
+            if !moved {
+                break;
+            }
+
+            // Merge each community into a single node, where edges within it become a loop.
+            let mut relabel: BTreeMap<usize, usize> = BTreeMap::new();
+
+            for c in community.iter() {
+                let next = relabel.len();
+                relabel.entry(*c).or_insert(next);
+            }
+
+            let mut merged: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); relabel.len()];
+
+            for (u, edges) in adjacent.iter().enumerate() {
+                for (v, w) in edges.iter() {
+                    *merged[relabel[&community[u]]]
+                        .entry(relabel[&community[*v]])
+                        .or_default() += w;
+                }
+            }
+
+            for c in membership.iter_mut() {
+                *c = relabel[&community[*c]];
+            }
+
+            adjacent = merged;
+        }
+
+        Self::partition(&nodes, &original, &membership)
+    }
+
+    /// This function finds communities using asynchronous label propagation. Every node starts with a label
+    /// of its own, and then in each round the nodes are visited in a random order, and each takes the label
+    /// that is most common among its neighbours, breaking ties at random. This stops once every node has one
+    /// of the most common labels among its neighbours, or after `LABEL_PROPAGATION_ROUNDS` rounds, and the
+    /// nodes sharing a label form a community.
+    ///
+    /// It runs in close to linear time, but the result depends on the random choices, which are made using
+    /// `seed` so that the same seed always gives the same partition. The graph is treated as undirected, in
+    /// the same way as `modularity`, and parallel edges make a neighbour's label more common.
+    ///
+    /// The function returns a `Partition`, holding the communities and their modularity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4, 5, 6, 7);
+    ///
+    /// for (u, v) in [(1, 2), (1, 3), (2, 3), (4, 5), (4, 6), (5, 6)] {
+    ///     g.add_undirected_edge(&u, &v, None);
+    /// }
+    ///
+    /// let partition = g.label_propagation(42);
+    ///
+    /// assert_eq!(partition.communities, vec![vec![&1, &2, &3], vec![&4, &5, &6], vec![&7]]);
+    /// assert_eq!(partition, g.label_propagation(42));
+    /// ```
+    pub fn label_propagation(&self, seed: u64) -> Partition<'_, N> {
+        let (nodes, adjacent) = self.weighted_undirected();
+        let n = nodes.len();
+        let mut labels: Vec<usize> = (0..n).collect();
+        let mut rng = Lcg::new(seed);
+        let mut changed = true;
+        let mut rounds = 0;
+
+        while changed && rounds < LABEL_PROPAGATION_ROUNDS {
+            changed = false;
+            rounds += 1;
+
+            // Shuffle the nodes with the Fisher-Yates shuffle.
+            let mut order: Vec<usize> = (0..n).collect();
+
+            for i in (1..n).rev() {
+                order.swap(i, rng.below(i as u64 + 1) as usize);
+            }
+
+            for u in order {
+                let mut counts: BTreeMap<usize, f64> = BTreeMap::new();
+
+                for (v, w) in adjacent[u].iter() {
+                    *counts.entry(labels[*v]).or_default() += w;
+                }
+
+                let Some(most) = counts.values().copied().reduce(f64::max) else {
+                    continue;
+                };
+
+                let common: Vec<usize> = counts
+                    .into_iter()
+                    .filter(|(_, count)| *count == most)
+                    .map(|(label, _)| label)
+                    .collect();
+
+                if !common.contains(&labels[u]) {
+                    labels[u] = common[rng.below(common.len() as u64) as usize];
+                    changed = true;
+                }
+            }
+        }
+
+        Self::partition(&nodes, &adjacent, &labels)
+    }
+
+    /// Returns the nodes in order, along with the number of edges between each pair of nodes when the graph
+    /// is treated as undirected. Both directions of each edge are stored, and loops are left out.
+    #[allow(clippy::type_complexity)]
+    fn weighted_undirected(&self) -> (Vec<&N>, Vec<BTreeMap<usize, f64>>) {
+        let nodes: Vec<&N> = self.nodes.iter().map(|n| &**n).collect();
+        let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let mut adjacent: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); nodes.len()];
+
+        for (u, v, _) in self.undirected_edges() {
+            let (u, v) = (index[u], index[v]);
+
+            *adjacent[u].entry(v).or_default() += 1.0;
+            *adjacent[v].entry(u).or_default() += 1.0;
+        }
+
+        (nodes, adjacent)
+    }
+
+    /// Finds the modularity of the communities given by `membership`, where a loop on a node counts once
+    /// towards its degree.
+    fn modularity_of(
+        adjacent: &[BTreeMap<usize, f64>],
+        membership: &[usize],
+        resolution: f64,
+    ) -> f64 {
+        let mut inside: BTreeMap<usize, f64> = BTreeMap::new();
+        let mut tot: BTreeMap<usize, f64> = BTreeMap::new();
+
+        for (u, edges) in adjacent.iter().enumerate() {
+            for (v, w) in edges.iter() {
+                *tot.entry(membership[u]).or_default() += w;
+
+                if membership[u] == membership[*v] {
+                    *inside.entry(membership[u]).or_default() += w;
+                }
+            }
+        }
+
+        let total: f64 = tot.values().sum();
+
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        tot.iter()
+            .map(|(c, t)| inside.get(c).unwrap_or(&0.0) / total - resolution * (t / total).powi(2))
+            .sum()
+    }
+
+    /// Groups the nodes by their community in `membership`, and scores the result.
+    fn partition<'a>(
+        nodes: &[&'a N],
+        adjacent: &[BTreeMap<usize, f64>],
+        membership: &[usize],
+    ) -> Partition<'a, N> {
+        let mut groups: BTreeMap<usize, Vec<&N>> = BTreeMap::new();
+
+        for (u, c) in membership.iter().enumerate() {
+            groups.entry(*c).or_default().push(nodes[u]);
+        }
+
+        let mut communities: Vec<Vec<&N>> = groups.into_values().collect();
+        communities.sort();
+
+        Partition {
+            communities,
+            modularity: Self::modularity_of(adjacent, membership, 1.0),
+        }
+    }
+}
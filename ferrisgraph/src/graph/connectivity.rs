@@ -0,0 +1,203 @@
+use super::Graph;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// The articulation points, bridges and biconnected components of a graph, which are all found by
+/// the same depth first search.
+struct Biconnectivity<'a, N, E> {
+    articulation_points: BTreeSet<&'a N>,
+    bridges: Vec<(&'a N, &'a N, &'a Option<E>)>,
+    components: Vec<Vec<&'a N>>,
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord,
+{
+    /// This function finds the articulation points of the graph, i.e. the nodes whose removal would split a
+    /// connected component into several. The graph is treated as undirected: the two directed edges added
+    /// by `add_undirected_edge` count as a single edge, and an edge added in only one direction can be used
+    /// either way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Core", "Edge1", "Edge2", "Office", "Lab");
+    /// g.add_undirected_edge(&"Core", &"Edge1", None);
+    /// g.add_undirected_edge(&"Core", &"Edge2", None);
+    /// g.add_undirected_edge(&"Edge1", &"Edge2", None);
+    /// g.add_undirected_edge(&"Edge1", &"Office", None);
+    /// g.add_undirected_edge(&"Office", &"Lab", None);
+    ///
+    /// assert_eq!(g.articulation_points(), BTreeSet::from([&"Edge1", &"Office"]));
+    /// ```
+    pub fn articulation_points(&self) -> BTreeSet<&N> {
+        self.biconnectivity().articulation_points
+    }
+
+    /// This function finds the bridges of the graph, i.e. the edges whose removal would split a connected
+    /// component into several. The graph is treated as undirected, in the same way as `articulation_points`,
+    /// so two undirected edges with different weights between the same nodes are never bridges.
+    ///
+    /// The bridges are returned as a sorted `Vec` of edges `(u, v, weight)`, where `u < v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Core", "Edge1", "Edge2", "Office", "Lab");
+    /// g.add_undirected_edge(&"Core", &"Edge1", None);
+    /// g.add_undirected_edge(&"Core", &"Edge2", None);
+    /// g.add_undirected_edge(&"Edge1", &"Edge2", None);
+    /// g.add_undirected_edge(&"Edge1", &"Office", Some(10));
+    /// g.add_edge(&"Lab", &"Office", Some(1));
+    ///
+    /// assert_eq!(g.bridges(), vec![(&"Edge1", &"Office", &Some(10)), (&"Lab", &"Office", &Some(1))]);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn bridges(&self) -> Vec<(&N, &N, &Option<E>)> {
+        self.biconnectivity().bridges
+    }
+
+    /// This function finds the biconnected components of the graph, i.e. the maximal sets of nodes that stay
+    /// connected when any one node is removed. Every edge belongs to exactly one component, so articulation
+    /// points belong to several, and a bridge forms a component on its own. Nodes without any edges are in
+    /// no component. The graph is treated as undirected, in the same way as `articulation_points`.
+    ///
+    /// The components are returned as a sorted `Vec<Vec<&N>>`, and the nodes within a component are sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Core", "Edge1", "Edge2", "Office", "Lab");
+    /// g.add_undirected_edge(&"Core", &"Edge1", None);
+    /// g.add_undirected_edge(&"Core", &"Edge2", None);
+    /// g.add_undirected_edge(&"Edge1", &"Edge2", None);
+    /// g.add_undirected_edge(&"Edge1", &"Office", None);
+    ///
+    /// assert_eq!(
+    ///     g.biconnected_components(),
+    ///     vec![vec![&"Core", &"Edge1", &"Edge2"], vec![&"Edge1", &"Office"]]
+    /// );
+    /// ```
+    pub fn biconnected_components(&self) -> Vec<Vec<&N>> {
+        self.biconnectivity().components
+    }
+
+    /// Performs an iterative version of Tarjan's depth first search on the undirected graph, where
+    /// `low[u]` is the earliest discovered node reachable from the subtree of `u` using one back edge.
+    /// Edges are told apart by their position in `edges`, so that parallel edges aren't mistaken for
+    /// the edge to the parent.
+    fn biconnectivity(&self) -> Biconnectivity<'_, N, E> {
+        let edges: Vec<(&N, &N, &Option<E>)> = self.undirected_edges().into_iter().collect();
+        let mut adjacent: HashMap<&N, Vec<(&N, usize)>> = HashMap::new();
+
+        for (id, (u, v, _)) in edges.iter().enumerate() {
+            adjacent.entry(u).or_default().push((v, id));
+            adjacent.entry(v).or_default().push((u, id));
+        }
+
+        let mut disc: HashMap<&N, usize> = HashMap::new();
+        let mut low: HashMap<&N, usize> = HashMap::new();
+        let mut result = Biconnectivity {
+            articulation_points: BTreeSet::new(),
+            bridges: Vec::new(),
+            components: Vec::new(),
+        };
+
+        for root in self.nodes.iter() {
+            let root = &**root;
+
+            if disc.contains_key(root) {
+                continue;
+            }
+
+            disc.insert(root, disc.len());
+            low.insert(root, disc[root]);
+
+            let mut root_children = 0;
+            let mut edge_stack: Vec<usize> = Vec::new();
+            let mut frames = vec![(root, None, adjacent.get(root).into_iter().flatten())];
+
+            while let Some((curr, parent_edge, curr_edges)) = frames.last_mut() {
+                let (curr, parent_edge) = (*curr, *parent_edge);
+
+                match curr_edges.next() {
+                    Some((_, id)) if Some(*id) == parent_edge => {}
+                    Some((next, id)) => match disc.get(next) {
+                        None => {
+                            disc.insert(next, disc.len());
+                            low.insert(next, disc[next]);
+                            edge_stack.push(*id);
+                            frames.push((
+                                next,
+                                Some(*id),
+                                adjacent.get(next).into_iter().flatten(),
+                            ));
+                        }
+                        Some(d) if *d < disc[curr] => {
+                            low.insert(curr, low[curr].min(*d));
+                            edge_stack.push(*id);
+                        }
+                        // An edge to a finished descendant, which was already seen from the other end.
+                        Some(_) => {}
+                    },
+                    None => {
+                        frames.pop();
+
+                        let Some((parent, _, _)) = frames.last() else {
+                            continue;
+                        };
+
+                        let (parent, tree_edge) =
+                            (*parent, parent_edge.expect("Only the root has no parent"));
+                        low.insert(parent, low[parent].min(low[curr]));
+
+                        if parent == root {
+                            root_children += 1;
+                        }
+
+                        if low[curr] > disc[parent] {
+                            result.bridges.push(edges[tree_edge]);
+                        }
+
+                        if low[curr] >= disc[parent] {
+                            if parent != root {
+                                result.articulation_points.insert(parent);
+                            }
+
+                            let mut component = BTreeSet::new();
+
+                            while let Some(id) = edge_stack.pop() {
+                                component.extend([edges[id].0, edges[id].1]);
+
+                                if id == tree_edge {
+                                    break;
+                                }
+                            }
+
+                            result.components.push(component.into_iter().collect());
+                        }
+                    }
+                }
+            }
+
+            if root_children > 1 {
+                result.articulation_points.insert(root);
+            }
+        }
+
+        result.bridges.sort();
+        result.components.sort();
+
+        result
+    }
+}
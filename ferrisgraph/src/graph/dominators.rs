@@ -0,0 +1,256 @@
+use super::graph::GraphError;
+use super::Graph;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// The dominators of a rooted directed graph, found by `dominators`.
+///
+/// A node `a` dominates a node `b` if every path from the root to `b` passes through `a`, so every node
+/// dominates itself. `idom` maps each node reachable from the root to its immediate dominator, i.e. the
+/// closest of the other nodes that dominate it, where the immediate dominator of the root is `None`.
+/// Together these form the dominator tree.
+#[derive(Debug)]
+pub struct Dominators<'a, N> {
+    pub root: &'a N,
+    pub idom: HashMap<&'a N, Option<&'a N>>,
+    /// The positions at which each node is entered and left by a depth first search of the dominator tree.
+    pub(crate) interval: HashMap<&'a N, (usize, usize)>,
+    pub(crate) frontiers: HashMap<&'a N, BTreeSet<&'a N>>,
+}
+
+impl<'a, N> Dominators<'a, N>
+where
+    N: Hash + Eq + Debug,
+{
+    /// Returns whether `a` dominates `b`, in constant time. Every reachable node dominates itself, and
+    /// nodes that can't be reached from the root neither dominate nor are dominated by any node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("entry", "then", "else", "exit");
+    /// g.add_edge(&"entry", &"then", None);
+    /// g.add_edge(&"entry", &"else", None);
+    /// g.add_edge(&"then", &"exit", None);
+    /// g.add_edge(&"else", &"exit", None);
+    ///
+    /// let dominators = g.dominators(&"entry").unwrap();
+    ///
+    /// assert!(dominators.dominates(&"entry", &"exit"));
+    /// assert!(dominators.dominates(&"then", &"then"));
+    /// assert!(!dominators.dominates(&"then", &"exit"));
+    /// ```
+    pub fn dominates(&self, a: &N, b: &N) -> bool {
+        match (self.interval.get(a), self.interval.get(b)) {
+            (Some((a_enter, a_exit)), Some((b_enter, b_exit))) => {
+                a_enter <= b_enter && b_exit <= a_exit
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the dominance frontier of `node`, i.e. the nodes `b` such that `node` dominates a predecessor
+    /// of `b`, but doesn't strictly dominate `b` itself. These are where the paths dominated by `node` meet
+    /// other paths, which is where phi functions are placed when building SSA form. Returns `None` if `node`
+    /// can't be reached from the root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("entry", "then", "else", "exit");
+    /// g.add_edge(&"entry", &"then", None);
+    /// g.add_edge(&"entry", &"else", None);
+    /// g.add_edge(&"then", &"exit", None);
+    /// g.add_edge(&"else", &"exit", None);
+    ///
+    /// let dominators = g.dominators(&"entry").unwrap();
+    ///
+    /// assert_eq!(dominators.dominance_frontier(&"then"), Some(&BTreeSet::from([&"exit"])));
+    /// assert_eq!(dominators.dominance_frontier(&"entry"), Some(&BTreeSet::new()));
+    /// ```
+    pub fn dominance_frontier(&self, node: &N) -> Option<&BTreeSet<&'a N>> {
+        self.frontiers.get(node)
+    }
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord,
+{
+    /// This function finds the dominators of every node reachable from `root`, using the algorithm of
+    /// Cooper, Harvey and Kennedy. It repeatedly sets the immediate dominator of each node, in reverse
+    /// postorder, to the closest common dominator of its predecessors, until nothing changes. This takes
+    /// `O(V^2)` time in the worst case, but only a couple of passes over the edges on typical control flow
+    /// graphs.
+    ///
+    /// The function returns a `Dominators`, holding the immediate dominator of each reachable node, which
+    /// can answer whether one node dominates another, and give the dominance frontier of a node.
+    /// `GraphError::NodeNotFound` is returned if `root` doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// // A loop whose body may exit early
+    /// let mut g: Graph<u32, &str> = graph_with_nodes!(0, 1, 2, 3, 4);
+    /// g.add_edge(&0, &1, Some("jump"));
+    /// g.add_edge(&1, &2, Some("branch"));
+    /// g.add_edge(&1, &4, Some("branch"));
+    /// g.add_edge(&2, &3, Some("jump"));
+    /// g.add_edge(&2, &4, Some("branch"));
+    /// g.add_edge(&3, &1, Some("jump"));
+    ///
+    /// let dominators = g.dominators(&0).unwrap();
+    ///
+    /// assert_eq!(dominators.idom[&0], None);
+    /// assert_eq!(dominators.idom[&3], Some(&2));
+    /// assert_eq!(dominators.idom[&4], Some(&1));
+    /// assert!(dominators.dominates(&1, &3));
+    /// ```
+    pub fn dominators<'a>(&'a self, root: &'a N) -> Result<Dominators<'a, N>, GraphError<'a, N>> {
+        let root = match self.nodes.get(root) {
+            Some(rc) => &**rc,
+            None => return Err(GraphError::NodeNotFound(root)),
+        };
+
+        // Number the reachable nodes in postorder, so that the root has the highest number.
+        let mut postorder: Vec<&N> = Vec::new();
+        let mut visited: BTreeSet<&N> = BTreeSet::from([root]);
+        let mut frames = vec![(root, self.edges[root].iter())];
+
+        while let Some((curr, curr_edges)) = frames.last_mut() {
+            match curr_edges.next() {
+                Some((dst, _)) => {
+                    if visited.insert(&**dst) {
+                        frames.push((&**dst, self.edges[&**dst].iter()));
+                    }
+                }
+                None => {
+                    postorder.push(*curr);
+                    frames.pop();
+                }
+            }
+        }
+
+        let number: HashMap<&N, usize> =
+            postorder.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let reversed = self.reverse_adjacency();
+        let preds: Vec<Vec<usize>> = postorder
+            .iter()
+            .map(|n| {
+                reversed[n]
+                    .iter()
+                    .filter_map(|p| number.get(p).copied())
+                    .collect()
+            })
+            .collect();
+
+        let r = postorder.len() - 1;
+        let mut idom: Vec<Option<usize>> = vec![None; postorder.len()];
+        idom[r] = Some(r);
+
+        // Walks up the dominator tree from both nodes until they meet, using the fact that dominators have
+        // higher postorder numbers.
+        let intersect = |idom: &[Option<usize>], mut a: usize, mut b: usize| {
+            while a != b {
+                while a < b {
+                    a = idom[a].expect("Processed nodes have a dominator");
+                }
+
+                while b < a {
+                    b = idom[b].expect("Processed nodes have a dominator");
+                }
+            }
+
+            a
+        };
+
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for u in (0..r).rev() {
+                let new_idom = preds[u].iter().filter(|p| idom[**p].is_some()).fold(
+                    None,
+                    |acc, p| match acc {
+                        None => Some(*p),
+                        Some(a) => Some(intersect(&idom, a, *p)),
+                    },
+                );
+
+                if new_idom != idom[u] {
+                    idom[u] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        idom[r] = None;
+
+        // Every node from a predecessor of `b` up to, but not including, the immediate dominator of `b` has
+        // `b` in its frontier.
+        let mut frontiers: Vec<BTreeSet<&N>> = vec![BTreeSet::new(); postorder.len()];
+
+        for b in 0..postorder.len() {
+            for p in preds[b].iter() {
+                let mut runner = Some(*p);
+
+                while runner != idom[b] {
+                    let Some(x) = runner else { break };
+
+                    frontiers[x].insert(postorder[b]);
+                    runner = idom[x];
+                }
+            }
+        }
+
+        // Number the nodes on entering and leaving them in a depth first search of the dominator tree.
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); postorder.len()];
+
+        for (u, d) in idom.iter().enumerate() {
+            if let Some(d) = d {
+                children[*d].push(u);
+            }
+        }
+
+        let mut interval: Vec<(usize, usize)> = vec![(0, 0); postorder.len()];
+        let mut counter = 0;
+        let mut stack = vec![(r, children[r].iter())];
+        interval[r].0 = counter;
+
+        while let Some((curr, curr_children)) = stack.last_mut() {
+            counter += 1;
+
+            match curr_children.next() {
+                Some(&child) => {
+                    interval[child].0 = counter;
+                    stack.push((child, children[child].iter()));
+                }
+                None => {
+                    interval[*curr].1 = counter;
+                    stack.pop();
+                }
+            }
+        }
+
+        Ok(Dominators {
+            root,
+            idom: postorder
+                .iter()
+                .zip(idom.iter())
+                .map(|(n, d)| (*n, d.map(|d| postorder[d])))
+                .collect(),
+            interval: postorder.iter().copied().zip(interval).collect(),
+            frontiers: postorder.iter().copied().zip(frontiers).collect(),
+        })
+    }
+}
@@ -0,0 +1,176 @@
+use super::graph::GraphError;
+use super::Graph;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord,
+{
+    /// This function finds an Eulerian circuit starting and ending at `start` using Hierholzer's algorithm,
+    /// i.e. a walk that uses every edge exactly once. Parallel edges with different weights are separate
+    /// edges, so each of them is used, and the two directed edges added by `add_undirected_edge` are used
+    /// once in each direction.
+    ///
+    /// The function returns the edges `(src, dst, weight)` in the order they are walked. Returned errors are:
+    /// - `GraphError::NodeNotFound` if `start` doesn't exist.
+    /// - `GraphError::UnbalancedDegree` if a node has a different number of incoming and outgoing edges.
+    /// - `GraphError::Disconnected` if some edges can't be reached from `start`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::{Graph, graph::GraphError}, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Depot", "Main St", "High St");
+    /// g.add_edge(&"Depot", &"Main St", None);
+    /// g.add_edge(&"Main St", &"High St", Some(1));
+    /// g.add_edge(&"Main St", &"High St", Some(2));
+    /// g.add_edge(&"High St", &"Main St", None);
+    /// g.add_edge(&"High St", &"Depot", None);
+    ///
+    /// let circuit = g.eulerian_circuit(&"Depot").unwrap();
+    ///
+    /// assert_eq!(circuit.len(), 5);
+    /// assert_eq!(circuit[0], (&"Depot", &"Main St", &None));
+    /// assert_eq!(circuit[4], (&"High St", &"Depot", &None));
+    ///
+    /// g.add_edge(&"Depot", &"High St", None);
+    /// assert_eq!(g.eulerian_circuit(&"Depot").unwrap_err(), GraphError::UnbalancedDegree(&"Depot", 1, 2));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn eulerian_circuit<'a>(
+        &'a self,
+        start: &'a N,
+    ) -> Result<Vec<(&'a N, &'a N, &'a Option<E>)>, GraphError<'a, N>> {
+        let start = match self.nodes.get(start) {
+            Some(rc) => &**rc,
+            None => return Err(GraphError::NodeNotFound(start)),
+        };
+
+        for (n, (in_degree, out_degree)) in self.degrees() {
+            if in_degree != out_degree {
+                return Err(GraphError::UnbalancedDegree(n, in_degree, out_degree));
+            }
+        }
+
+        self.hierholzer(start)
+    }
+
+    /// This function finds an Eulerian path using Hierholzer's algorithm, i.e. a walk that uses every edge
+    /// exactly once, but may end at a different node to where it started. Edges are treated the same as in
+    /// `eulerian_circuit`.
+    ///
+    /// The path starts at the node with one more outgoing edge than incoming edges if there is one, and
+    /// otherwise it is a circuit starting at the smallest node with an outgoing edge. Returned errors are:
+    /// - `GraphError::UnbalancedDegree` if a node's incoming and outgoing edges differ by more than one, or
+    ///   if more than one node could be the start or end of the path.
+    /// - `GraphError::Disconnected` if some edges can't be reached from the start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4);
+    /// g.add_edge(&2, &1, None);
+    /// g.add_edge(&1, &3, None);
+    /// g.add_edge(&3, &2, None);
+    /// g.add_edge(&2, &4, None);
+    ///
+    /// let path = g.eulerian_path().unwrap();
+    ///
+    /// assert_eq!(path, vec![(&2, &1, &None), (&1, &3, &None), (&3, &2, &None), (&2, &4, &None)]);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn eulerian_path(&self) -> Result<Vec<(&N, &N, &Option<E>)>, GraphError<'_, N>> {
+        let mut start = None;
+        let mut end = None;
+
+        for (n, (in_degree, out_degree)) in self.degrees() {
+            let unbalanced = Err(GraphError::UnbalancedDegree(n, in_degree, out_degree));
+
+            if out_degree == in_degree + 1 {
+                if start.replace(n).is_some() {
+                    return unbalanced;
+                }
+            } else if in_degree == out_degree + 1 {
+                if end.replace(n).is_some() {
+                    return unbalanced;
+                }
+            } else if in_degree != out_degree {
+                return unbalanced;
+            }
+        }
+
+        let start = start.or_else(|| {
+            self.edges
+                .iter()
+                .find(|(_, set)| !set.is_empty())
+                .map(|(n, _)| &**n)
+        });
+
+        match start {
+            Some(start) => self.hierholzer(start),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Maps every node to its in-degree and out-degree, in the order of the nodes.
+    fn degrees(&self) -> Vec<(&N, (usize, usize))> {
+        let reversed = self.reverse_adjacency();
+
+        self.nodes
+            .iter()
+            .map(|n| (&**n, (reversed[&**n].len(), self.edges[n].len())))
+            .collect()
+    }
+
+    /// Walks every edge reachable from `start` using Hierholzer's algorithm with an explicit stack. Whenever
+    /// a node has no unused edges left, the edge it was reached by is added to the end of the walk, so the
+    /// walk is built in reverse. The degrees of the nodes must already have been checked.
+    #[allow(clippy::type_complexity)]
+    fn hierholzer<'a>(
+        &'a self,
+        start: &'a N,
+    ) -> Result<Vec<(&'a N, &'a N, &'a Option<E>)>, GraphError<'a, N>> {
+        let mut unused: HashMap<&N, _> = self
+            .edges
+            .iter()
+            .map(|(n, set)| (&**n, set.iter()))
+            .collect();
+
+        let mut walk = Vec::new();
+        let mut visited: HashSet<&N> = HashSet::from([start]);
+        let mut stack = vec![(start, None)];
+
+        while let Some((curr, edge)) = stack.last() {
+            let (curr, edge) = (*curr, *edge);
+
+            match unused.get_mut(curr).and_then(|edges| edges.next()) {
+                Some((dst, e)) => {
+                    visited.insert(&**dst);
+                    stack.push((&**dst, Some((curr, &**dst, e))));
+                }
+                None => {
+                    stack.pop();
+                    walk.extend(edge);
+                }
+            }
+        }
+
+        if let Some((n, _)) = self
+            .edges
+            .iter()
+            .find(|(n, set)| !set.is_empty() && !visited.contains(&***n))
+        {
+            return Err(GraphError::Disconnected(start, n));
+        }
+
+        walk.reverse();
+
+        Ok(walk)
+    }
+}
@@ -0,0 +1,120 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Neg, Sub};
+
+/// A wrapper around `f32` or `f64` that implements `Hash`, `Eq` and `Ord`, so that floating point
+/// numbers can be used as edge weights.
+///
+/// Numbers are ordered as usual, with `-0.0` equal to `0.0`. All `NaN`s are equal to each other,
+/// and are greater than every other number, including infinity.
+///
+/// # Examples
+///
+/// ```
+/// use ferrisgraph::{graph::{Graph, OrderedFloat}, graph_with_nodes};
+///
+/// let mut g: Graph<&str, OrderedFloat<f64>> = graph_with_nodes!("Paris", "Lyon", "Marseille");
+///
+/// g.add_undirected_edge(&"Paris", &"Lyon", Some(OrderedFloat(391.5)));
+/// g.add_undirected_edge(&"Lyon", &"Marseille", Some(OrderedFloat(277.25)));
+/// g.add_undirected_edge(&"Paris", &"Marseille", Some(OrderedFloat(775.0)));
+///
+/// let paths = g.djikstra(&"Paris").unwrap();
+///
+/// assert_eq!(paths.dist[&"Marseille"], OrderedFloat(668.75));
+/// assert!(OrderedFloat(f64::NAN) > OrderedFloat(f64::INFINITY));
+/// assert_eq!(OrderedFloat(-0.0), OrderedFloat(0.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OrderedFloat<F>(pub F);
+
+macro_rules! impl_ordered_float {
+    ($f:ty) => {
+        impl PartialEq for OrderedFloat<$f> {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for OrderedFloat<$f> {}
+
+        impl PartialOrd for OrderedFloat<$f> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for OrderedFloat<$f> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                match (self.0.is_nan(), other.0.is_nan()) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Greater,
+                    (false, true) => Ordering::Less,
+                    (false, false) => self
+                        .0
+                        .partial_cmp(&other.0)
+                        .expect("Numbers that aren't NaN are comparable"),
+                }
+            }
+        }
+
+        impl Hash for OrderedFloat<$f> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                // Equal values must hash the same, so every NaN, and both zeroes, share their bits.
+                let bits = if self.0.is_nan() {
+                    <$f>::NAN.to_bits()
+                } else {
+                    (self.0 + 0.0).to_bits()
+                };
+
+                bits.hash(state);
+            }
+        }
+
+        impl Add for OrderedFloat<$f> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                OrderedFloat(self.0 + other.0)
+            }
+        }
+
+        impl Sub for OrderedFloat<$f> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                OrderedFloat(self.0 - other.0)
+            }
+        }
+
+        impl Neg for OrderedFloat<$f> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                OrderedFloat(-self.0)
+            }
+        }
+
+        impl From<$f> for OrderedFloat<$f> {
+            fn from(value: $f) -> Self {
+                OrderedFloat(value)
+            }
+        }
+
+        impl From<OrderedFloat<$f>> for $f {
+            fn from(value: OrderedFloat<$f>) -> Self {
+                value.0
+            }
+        }
+
+        impl Display for OrderedFloat<$f> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+
+impl_ordered_float!(f32);
+impl_ordered_float!(f64);
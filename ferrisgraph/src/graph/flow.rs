@@ -0,0 +1,604 @@
+use super::graph::GraphError;
+use super::measure::Measure;
+use super::Graph;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Sub;
+
+/// A maximum flow from a source to a sink, found by `dinic` or `edmonds_karp`.
+///
+/// `value` is the total flow leaving the source. `flow` maps every pair `(u, v)` with an edge of positive
+/// capacity from `u` to `v` to the flow sent along it, where parallel edges are treated as a single edge
+/// with their summed capacity.
+///
+/// `source_side` holds the nodes on the source side of a minimum cut, i.e. the nodes that can still be
+/// reached from the source once the flow is sent, and `cut` holds the edges `(u, v, capacity)` leaving it.
+/// By the max-flow min-cut theorem, the capacities in `cut` add up to `value`.
+#[derive(Debug)]
+pub struct MaxFlow<'a, N, E> {
+    pub value: E,
+    pub flow: HashMap<(&'a N, &'a N), E>,
+    pub source_side: BTreeSet<&'a N>,
+    pub cut: Vec<(&'a N, &'a N, E)>,
+}
+
+/// A minimum cost flow, found by `min_cost_flow`.
+///
+/// `flow` maps every edge `(src, dst, weight)` of the graph to the number of units sent along it,
+/// and `cost` is the total cost of sending them.
+#[derive(Debug)]
+pub struct MinCostFlow<'a, N, E> {
+    #[allow(clippy::type_complexity)]
+    pub flow: HashMap<(&'a N, &'a N, &'a Option<E>), i64>,
+    pub cost: i64,
+}
+
+/// The residual network used by the max flow algorithms. Nodes are numbered by their position in
+/// `nodes`, and each pair of nodes with an edge in either direction gets two arcs, `a` and `a ^ 1`,
+/// which are each other's reverse.
+struct FlowNetwork<'a, N, E> {
+    nodes: Vec<&'a N>,
+    adjacent: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    capacity: Vec<E>,
+    residual: Vec<E>,
+}
+
+impl<'a, N, E> FlowNetwork<'a, N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord + Measure + Sub<Output = E>,
+{
+    fn new(graph: &'a Graph<N, E>) -> Self {
+        let nodes: Vec<&N> = graph.nodes.iter().map(|n| &**n).collect();
+        let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+
+        // Sum the capacities of parallel edges, keyed by the unordered pair of nodes.
+        let mut pairs: BTreeMap<(usize, usize), (E, E)> = BTreeMap::new();
+
+        for (src, set) in graph.edges.iter() {
+            for (dst, e) in set.iter() {
+                let (u, v) = (index[&**src], index[&**dst]);
+
+                if u == v {
+                    continue;
+                }
+
+                let c = e.clone().unwrap_or_else(E::default_weight).max(E::zero());
+                let pair = pairs
+                    .entry((u.min(v), u.max(v)))
+                    .or_insert_with(|| (E::zero(), E::zero()));
+
+                if u < v {
+                    pair.0 = pair.0.saturating_add(&c);
+                } else {
+                    pair.1 = pair.1.saturating_add(&c);
+                }
+            }
+        }
+
+        let mut network = FlowNetwork {
+            nodes,
+            adjacent: vec![Vec::new(); index.len()],
+            to: Vec::new(),
+            capacity: Vec::new(),
+            residual: Vec::new(),
+        };
+
+        for ((u, v), (forward, backward)) in pairs {
+            network.adjacent[u].push(network.to.len());
+            network.to.push(v);
+            network.capacity.push(forward.clone());
+            network.residual.push(forward);
+
+            network.adjacent[v].push(network.to.len());
+            network.to.push(u);
+            network.capacity.push(backward.clone());
+            network.residual.push(backward);
+        }
+
+        network
+    }
+
+    /// Sends `amount` of flow along every arc in `path`.
+    fn augment(&mut self, path: &[usize], amount: &E) {
+        for &a in path {
+            self.residual[a] = self.residual[a].clone() - amount.clone();
+            self.residual[a ^ 1] = self.residual[a ^ 1].saturating_add(amount);
+        }
+    }
+
+    /// Returns the smallest residual capacity along `path`.
+    fn bottleneck(&self, path: &[usize]) -> E {
+        path.iter()
+            .map(|&a| self.residual[a].clone())
+            .min()
+            .expect("An augmenting path has at least one arc")
+    }
+
+    /// Finds the distance of every node from `s` using arcs with residual capacity, where unreached
+    /// nodes have a distance of `usize::MAX`. Also returns the arc each node was reached through.
+    fn levels(&self, s: usize) -> (Vec<usize>, Vec<Option<usize>>) {
+        let mut level = vec![usize::MAX; self.nodes.len()];
+        let mut via = vec![None; self.nodes.len()];
+        let mut queue = VecDeque::from([s]);
+        level[s] = 0;
+
+        while let Some(u) = queue.pop_front() {
+            for &a in self.adjacent[u].iter() {
+                let v = self.to[a];
+
+                if level[v] == usize::MAX && self.residual[a] > E::zero() {
+                    level[v] = level[u] + 1;
+                    via[v] = Some(a);
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        (level, via)
+    }
+
+    /// Builds the result once no augmenting path is left.
+    fn into_max_flow(self, s: usize) -> MaxFlow<'a, N, E> {
+        let (level, _) = self.levels(s);
+        let reached = |u: usize| level[u] != usize::MAX;
+
+        let (mut out, mut into) = (E::zero(), E::zero());
+        let mut flow = HashMap::new();
+        let mut cut = Vec::new();
+
+        for a in 0..self.to.len() {
+            let (u, v) = (self.to[a ^ 1], self.to[a]);
+
+            if self.capacity[a] <= E::zero() {
+                continue;
+            }
+
+            // Flow along the reverse arc cancels flow along this one, which can leave more residual
+            // capacity than the arc started with.
+            let sent = if self.residual[a] < self.capacity[a] {
+                self.capacity[a].clone() - self.residual[a].clone()
+            } else {
+                E::zero()
+            };
+
+            if u == s {
+                out = out.saturating_add(&sent);
+            } else if v == s {
+                into = into.saturating_add(&sent);
+            }
+
+            if reached(u) && !reached(v) {
+                cut.push((self.nodes[u], self.nodes[v], self.capacity[a].clone()));
+            }
+
+            flow.insert((self.nodes[u], self.nodes[v]), sent);
+        }
+
+        cut.sort();
+
+        let source_side = (0..self.nodes.len())
+            .filter(|u| reached(*u))
+            .map(|u| self.nodes[u])
+            .collect();
+
+        MaxFlow {
+            value: out - into,
+            flow,
+            source_side,
+            cut,
+        }
+    }
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord + Measure + Sub<Output = E>,
+{
+    /// This function finds a maximum flow from `src` to `sink`, using edge weights as capacities.
+    /// It is equivalent to `dinic`.
+    ///
+    /// Unweighted edges have a capacity of `Measure::default_weight`, edges with negative weights have a
+    /// capacity of zero, and loops are ignored. Parallel edges from one node to another are treated as a
+    /// single edge with their summed capacity. If `src` and `sink` are the same node, no flow is sent.
+    ///
+    /// The function returns a `MaxFlow`, holding the flow value, the flow along each edge, and a minimum cut.
+    /// `GraphError::NodeNotFound` is returned if either node doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, u32> = graph_with_nodes!("Server", "Router", "Switch", "Client");
+    /// g.add_edge(&"Server", &"Router", Some(100));
+    /// g.add_edge(&"Server", &"Switch", Some(40));
+    /// g.add_edge(&"Router", &"Switch", Some(25));
+    /// g.add_edge(&"Router", &"Client", Some(50));
+    /// g.add_edge(&"Router", &"Client", Some(10));
+    /// g.add_edge(&"Switch", &"Client", Some(60));
+    ///
+    /// let flow = g.max_flow(&"Server", &"Client").unwrap();
+    ///
+    /// assert_eq!(flow.value, 120);
+    /// assert_eq!(flow.flow[&(&"Router", &"Client")], 60);
+    /// assert_eq!(flow.cut, vec![(&"Router", &"Client", 60), (&"Switch", &"Client", 60)]);
+    /// ```
+    pub fn max_flow<'a>(
+        &'a self,
+        src: &'a N,
+        sink: &'a N,
+    ) -> Result<MaxFlow<'a, N, E>, GraphError<'a, N>> {
+        self.dinic(src, sink)
+    }
+
+    /// This function finds a maximum flow from `src` to `sink` using the Edmonds-Karp algorithm, which
+    /// repeatedly sends flow along the shortest path with spare capacity, found by breadth first search.
+    /// It runs in `O(V * E^2)` time.
+    ///
+    /// Capacities and the return value are the same as `max_flow`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4);
+    /// g.add_edge(&1, &2, Some(3));
+    /// g.add_edge(&1, &3, Some(2));
+    /// g.add_edge(&2, &3, Some(5));
+    /// g.add_edge(&2, &4, Some(2));
+    /// g.add_edge(&3, &4, Some(3));
+    ///
+    /// let flow = g.edmonds_karp(&1, &4).unwrap();
+    ///
+    /// assert_eq!(flow.value, 5);
+    /// assert_eq!(flow.source_side.into_iter().collect::<Vec<_>>(), vec![&1]);
+    /// assert!(g.edmonds_karp(&1, &5).is_err());
+    /// ```
+    pub fn edmonds_karp<'a>(
+        &'a self,
+        src: &'a N,
+        sink: &'a N,
+    ) -> Result<MaxFlow<'a, N, E>, GraphError<'a, N>> {
+        let (mut network, s, t) = self.flow_network(src, sink)?;
+
+        if s != t {
+            loop {
+                let (_, via) = network.levels(s);
+
+                if via[t].is_none() {
+                    break;
+                }
+
+                let mut path = Vec::new();
+                let mut curr = t;
+
+                while let Some(a) = via[curr] {
+                    path.push(a);
+                    curr = network.to[a ^ 1];
+                }
+
+                let amount = network.bottleneck(&path);
+                network.augment(&path, &amount);
+            }
+        }
+
+        Ok(network.into_max_flow(s))
+    }
+
+    /// This function finds a maximum flow from `src` to `sink` using Dinic's algorithm, which builds a
+    /// level graph of shortest paths with breadth first search and then saturates it with a blocking flow.
+    /// It runs in `O(V^2 * E)` time, and is much faster than `edmonds_karp` in practice.
+    ///
+    /// Capacities and the return value are the same as `max_flow`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<char, i32> = graph_with_nodes!('s', 'a', 'b', 't');
+    /// g.add_edge(&'s', &'a', Some(10));
+    /// g.add_edge(&'s', &'b', Some(10));
+    /// g.add_edge(&'a', &'b', Some(1));
+    /// g.add_edge(&'a', &'t', Some(4));
+    /// g.add_edge(&'b', &'t', Some(10));
+    ///
+    /// let flow = g.dinic(&'s', &'t').unwrap();
+    ///
+    /// assert_eq!(flow.value, 14);
+    /// assert_eq!(flow.cut, vec![(&'a', &'t', 4), (&'b', &'t', 10)]);
+    /// assert_eq!(flow.flow[&(&'a', &'b')], 0);
+    /// ```
+    pub fn dinic<'a>(
+        &'a self,
+        src: &'a N,
+        sink: &'a N,
+    ) -> Result<MaxFlow<'a, N, E>, GraphError<'a, N>> {
+        let (mut network, s, t) = self.flow_network(src, sink)?;
+
+        if s != t {
+            loop {
+                let (level, _) = network.levels(s);
+
+                if level[t] == usize::MAX {
+                    break;
+                }
+
+                // Find a blocking flow with an explicit stack of arcs from `s`. `next_arc[u]` is the first arc
+                // out of `u` that hasn't been found to be saturated or to lead to a dead end.
+                let mut next_arc = vec![0; network.nodes.len()];
+                let mut path: Vec<usize> = Vec::new();
+
+                loop {
+                    let u = path.last().map_or(s, |a| network.to[*a]);
+
+                    if u == t {
+                        let amount = network.bottleneck(&path);
+                        network.augment(&path, &amount);
+
+                        // Retreat to just before the first arc that is now saturated.
+                        let saturated = path
+                            .iter()
+                            .position(|a| network.residual[*a] <= E::zero())
+                            .expect("The bottleneck arc is saturated");
+                        path.truncate(saturated);
+
+                        continue;
+                    }
+
+                    let arc = network.adjacent[u][next_arc[u]..]
+                        .iter()
+                        .position(|a| {
+                            network.residual[*a] > E::zero()
+                                && level[network.to[*a]] == level[u] + 1
+                        })
+                        .map(|offset| next_arc[u] + offset);
+
+                    match arc {
+                        Some(i) => {
+                            next_arc[u] = i;
+                            path.push(network.adjacent[u][i]);
+                        }
+                        None if u == s => break,
+                        None => {
+                            next_arc[u] = network.adjacent[u].len();
+
+                            let a = path.pop().expect("Only the source has an empty path");
+                            next_arc[network.to[a ^ 1]] += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(network.into_max_flow(s))
+    }
+
+    /// Builds the residual network for a flow from `src` to `sink`, returning it along with their indices.
+    fn flow_network<'a>(
+        &'a self,
+        src: &'a N,
+        sink: &'a N,
+    ) -> Result<(FlowNetwork<'a, N, E>, usize, usize), GraphError<'a, N>> {
+        for n in [src, sink] {
+            if !self.nodes.contains(n) {
+                return Err(GraphError::NodeNotFound(n));
+            }
+        }
+
+        let network = FlowNetwork::new(self);
+        let s = network.nodes.binary_search(&src).expect("src exists");
+        let t = network.nodes.binary_search(&sink).expect("sink exists");
+
+        Ok((network, s, t))
+    }
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord,
+{
+    /// This function finds the cheapest flow that meets every supply and demand, using successive
+    /// shortest paths with Djikstra's algorithm.
+    ///
+    /// `supplies` maps nodes to the number of units they supply if positive, or demand if negative, where
+    /// missing nodes neither supply nor demand anything. `capacity` and `cost` give the capacity and the
+    /// per-unit cost of an edge from its weight, so both can be stored in `E`. Edges with negative
+    /// capacities are treated as having a capacity of zero, and costs may be negative.
+    ///
+    /// The function returns a `MinCostFlow`, holding the flow along each edge and the total cost.
+    /// `GraphError::InfeasibleFlow` is returned with the number of units that can't be sent if the
+    /// supplies and demands don't add up to zero, or if the capacities are too small to meet them.
+    /// `GraphError::NodeNotFound` is returned if a node in `supplies` doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::{Graph, graph::GraphError}, graph_with_nodes};
+    /// use std::collections::HashMap;
+    ///
+    /// // Each route is weighted by (capacity, cost per unit)
+    /// let mut g: Graph<&str, (i64, i64)> = graph_with_nodes!("Factory", "Rail", "Road", "Store");
+    /// g.add_edge(&"Factory", &"Rail", Some((15, 4)));
+    /// g.add_edge(&"Factory", &"Road", Some((8, 4)));
+    /// g.add_edge(&"Rail", &"Store", Some((10, 1)));
+    /// g.add_edge(&"Road", &"Store", Some((20, 3)));
+    ///
+    /// let supplies = HashMap::from([(&"Factory", 12), (&"Store", -12)]);
+    /// let capacity = |e: &Option<(i64, i64)>| e.unwrap().0;
+    /// let cost = |e: &Option<(i64, i64)>| e.unwrap().1;
+    ///
+    /// let res = g.min_cost_flow(&supplies, capacity, cost).unwrap();
+    ///
+    /// assert_eq!(res.cost, 10 * 5 + 2 * 7);
+    /// assert_eq!(res.flow[&(&"Rail", &"Store", &Some((10, 1)))], 10);
+    /// assert_eq!(res.flow[&(&"Factory", &"Road", &Some((8, 4)))], 2);
+    ///
+    /// let supplies = HashMap::from([(&"Factory", 30), (&"Store", -30)]);
+    /// assert_eq!(g.min_cost_flow(&supplies, capacity, cost).unwrap_err(), GraphError::InfeasibleFlow(12));
+    /// ```
+    pub fn min_cost_flow<'a, C, K>(
+        &'a self,
+        supplies: &HashMap<&'a N, i64>,
+        capacity: C,
+        cost: K,
+    ) -> Result<MinCostFlow<'a, N, E>, GraphError<'a, N>>
+    where
+        C: Fn(&Option<E>) -> i64,
+        K: Fn(&Option<E>) -> i64,
+    {
+        let nodes: Vec<&N> = self.nodes.iter().map(|n| &**n).collect();
+        let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let mut excess = vec![0; nodes.len() + 2];
+
+        for (n, supply) in supplies.iter() {
+            match index.get(*n) {
+                Some(i) => excess[*i] += supply,
+                None => return Err(GraphError::NodeNotFound(n)),
+            }
+        }
+
+        // Each edge gets an arc `a` and a reverse arc `a ^ 1`, holding residual capacities and costs.
+        let mut edges = Vec::new();
+        let mut adjacent: Vec<Vec<usize>> = vec![Vec::new(); nodes.len() + 2];
+        let mut to: Vec<usize> = Vec::new();
+        let mut residual: Vec<i64> = Vec::new();
+        let mut arc_cost: Vec<i64> = Vec::new();
+
+        for (src, set) in self.edges.iter() {
+            for (dst, e) in set.iter() {
+                let (u, v) = (index[&**src], index[&**dst]);
+                let (c, k) = (capacity(e).max(0), cost(e));
+
+                adjacent[u].push(to.len());
+                adjacent[v].push(to.len() + 1);
+                to.extend([v, u]);
+                residual.extend([c, 0]);
+                arc_cost.extend([k, -k]);
+
+                edges.push((&**src, &**dst, e, c));
+            }
+        }
+
+        // Saturate every arc with a negative cost up front, so that all residual costs are non-negative.
+        for a in (0..to.len()).step_by(2) {
+            if arc_cost[a] < 0 && residual[a] > 0 {
+                let (u, v, c) = (to[a ^ 1], to[a], residual[a]);
+
+                excess[u] -= c;
+                excess[v] += c;
+                residual[a] = 0;
+                residual[a ^ 1] = c;
+            }
+        }
+
+        // Connect a super source to every node with spare supply, and every node with unmet demand
+        // to a super sink.
+        let (s, t) = (nodes.len(), nodes.len() + 1);
+        let (mut supply, mut demand) = (0, 0);
+
+        for (u, ex) in excess.clone().into_iter().enumerate() {
+            let a = to.len();
+
+            if ex > 0 {
+                supply += ex;
+                adjacent[s].push(a);
+                adjacent[u].push(a + 1);
+                to.extend([u, s]);
+                residual.extend([ex, 0]);
+                arc_cost.extend([0, 0]);
+            } else if ex < 0 {
+                demand -= ex;
+                adjacent[u].push(a);
+                adjacent[t].push(a + 1);
+                to.extend([t, u]);
+                residual.extend([-ex, 0]);
+                arc_cost.extend([0, 0]);
+            }
+        }
+
+        // Send flow along the cheapest path from `s` to `t` until none is left. Potentials keep the
+        // reduced cost of every residual arc non-negative, so that Djikstra's algorithm can be used.
+        let mut potential = vec![0; nodes.len() + 2];
+        let mut sent = 0;
+
+        loop {
+            let mut dist: Vec<Option<i64>> = vec![None; nodes.len() + 2];
+            let mut via: Vec<Option<usize>> = vec![None; nodes.len() + 2];
+            let mut pq = BinaryHeap::from([Reverse((0, s))]);
+            dist[s] = Some(0);
+
+            while let Some(Reverse((d, u))) = pq.pop() {
+                if dist[u] != Some(d) {
+                    continue;
+                }
+
+                for &a in adjacent[u].iter() {
+                    let v = to[a];
+
+                    if residual[a] <= 0 {
+                        continue;
+                    }
+
+                    let next = d + arc_cost[a] + potential[u] - potential[v];
+
+                    if dist[v].is_none_or(|curr| next < curr) {
+                        dist[v] = Some(next);
+                        via[v] = Some(a);
+                        pq.push(Reverse((next, v)));
+                    }
+                }
+            }
+
+            if dist[t].is_none() {
+                break;
+            }
+
+            for (u, d) in dist.iter().enumerate() {
+                if let Some(d) = d {
+                    potential[u] += d;
+                }
+            }
+
+            let mut path = Vec::new();
+            let mut curr = t;
+
+            while let Some(a) = via[curr] {
+                path.push(a);
+                curr = to[a ^ 1];
+            }
+
+            let amount = path.iter().map(|a| residual[*a]).min().unwrap_or(0);
+
+            for a in path {
+                residual[a] -= amount;
+                residual[a ^ 1] += amount;
+            }
+
+            sent += amount;
+        }
+
+        if sent < supply.max(demand) {
+            return Err(GraphError::InfeasibleFlow(supply.max(demand) - sent));
+        }
+
+        let mut flow = HashMap::new();
+        let mut total = 0;
+
+        for (i, (src, dst, e, c)) in edges.into_iter().enumerate() {
+            let units = c - residual[2 * i];
+
+            total += units * cost(e);
+            flow.insert((src, dst, e), units);
+        }
+
+        Ok(MinCostFlow { flow, cost: total })
+    }
+}
@@ -12,6 +12,7 @@ use thiserror::Error;
 ///
 /// It is required that the node type implements Hash, Eq, Ord and Debug.
 /// It is required that the edge type implements Hash, Eq, Ord and Debug.
+/// Floating point weights can be used by wrapping them in `OrderedFloat`.
 
 #[derive(PartialEq, Debug)]
 pub struct Graph<N, E>
@@ -20,6 +21,7 @@ where
     E: Hash + Eq + Ord,
 {
     pub(crate) nodes: BTreeSet<Rc<N>>,
+    #[allow(clippy::type_complexity)]
     pub(crate) edges: BTreeMap<Rc<N>, BTreeSet<(Rc<N>, Option<E>)>>,
 }
 
@@ -30,6 +32,46 @@ where
 {
     #[error("Node {:?} does not exist.", _0)]
     NodeNotFound(&'a N),
+    #[error("Negative cycle {:?} exists.", _0)]
+    NegativeCycle(Vec<&'a N>),
+    #[error("The length of a path is too small or too large for the weight type.")]
+    WeightOverflow,
+    #[error("No flow meets every supply and demand, leaving {:?} units unmet.", _0)]
+    InfeasibleFlow(i64),
+    #[error("Node {:?} is given more than once.", _0)]
+    DuplicateNode(&'a N),
+    #[error("Node {:?} has {} incoming and {} outgoing edges.", _0, _1, _2)]
+    UnbalancedDegree(&'a N, usize, usize),
+    #[error("The edges of node {:?} can't be reached from node {:?}.", _1, _0)]
+    Disconnected(&'a N, &'a N),
+    #[error("The graph has {} nodes, but at most {} are supported.", _0, _1)]
+    TooManyNodes(usize, usize),
+    #[error("The tour doesn't visit every node exactly once along existing edges and return to its start.")]
+    InvalidTour,
+    #[error("Node {:?} is in more than one community.", _0)]
+    OverlappingCommunities(&'a N),
+}
+
+/// The error returned when an operation requiring an acyclic graph finds a cycle, or when `bipartition`
+/// finds a cycle of odd length. `cycle` is a path through the graph that starts and ends at the same node,
+/// e.g. `[a, b, c, a]`.
+#[derive(Debug, Error, PartialEq)]
+#[error("Graph contains a cycle: {:?}", cycle)]
+pub struct CycleError<'a, N>
+where
+    N: Debug,
+{
+    pub cycle: Vec<&'a N>,
+}
+
+impl<N, E> Default for Graph<N, E>
+where
+    N: Hash + Eq + Debug + Ord,
+    E: Hash + Eq + Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<N, E> Graph<N, E>
@@ -274,6 +316,7 @@ where
     /// assert_eq!(new_g, g);
     ///
     /// ```
+    #[allow(clippy::should_implement_trait)]
     pub fn clone(&self) -> Self {
         Graph {
             nodes: self.nodes.clone(),
@@ -0,0 +1,531 @@
+use super::graph::{CycleError, GraphError};
+use super::measure::Measure;
+use super::Graph;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Sub;
+
+/// A matching of minimum total weight, found by `min_weight_assignment`.
+///
+/// `pairs` holds the matched pairs `(left, right, weight)`, sorted by the left node, where `weight` is the
+/// edge between them that was used. `weight` is the total weight of those edges.
+#[derive(Debug, PartialEq)]
+pub struct Assignment<'a, N, E> {
+    pub pairs: Vec<(&'a N, &'a N, &'a Option<E>)>,
+    pub weight: E,
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord,
+{
+    /// This function splits the nodes of the graph into two sides, so that every edge goes between the
+    /// two sides, ignoring the direction of edges. The smallest node of each connected component is put
+    /// on the first side.
+    ///
+    /// The function returns a tuple `(left, right)` of type `(BTreeSet<&N>, BTreeSet<&N>)` on success.
+    /// If the graph isn't bipartite, a `CycleError` is returned holding a cycle of odd length, such as
+    /// `[a, b, c, a]`, whose edges may go in either direction. A loop is a cycle of length one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Ana", "Ben", "Cleaning", "Driving");
+    /// g.add_edge(&"Ana", &"Cleaning", None);
+    /// g.add_edge(&"Ana", &"Driving", None);
+    /// g.add_edge(&"Ben", &"Driving", None);
+    ///
+    /// let (left, right) = g.bipartition().unwrap();
+    ///
+    /// assert_eq!(left, BTreeSet::from([&"Ana", &"Ben"]));
+    /// assert_eq!(right, BTreeSet::from([&"Cleaning", &"Driving"]));
+    ///
+    /// g.add_edge(&"Cleaning", &"Driving", None);
+    ///
+    /// let err = g.bipartition().unwrap_err();
+    /// assert_eq!(err.cycle, vec![&"Cleaning", &"Ana", &"Driving", &"Cleaning"]);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn bipartition(&self) -> Result<(BTreeSet<&N>, BTreeSet<&N>), CycleError<'_, N>> {
+        for (src, set) in self.edges.iter() {
+            if set.iter().any(|(dst, _)| dst == src) {
+                return Err(CycleError {
+                    cycle: vec![&**src, &**src],
+                });
+            }
+        }
+
+        let adjacent = self.undirected_adjacency();
+        let mut side: HashMap<&N, bool> = HashMap::new();
+        let mut parent: HashMap<&N, &N> = HashMap::new();
+
+        for root in self.nodes.iter() {
+            let root = &**root;
+
+            if side.contains_key(root) {
+                continue;
+            }
+
+            side.insert(root, false);
+            let mut queue = VecDeque::from([root]);
+
+            while let Some(curr) = queue.pop_front() {
+                for next in adjacent[curr].iter() {
+                    match side.get(next) {
+                        None => {
+                            side.insert(next, !side[curr]);
+                            parent.insert(next, curr);
+                            queue.push_back(next);
+                        }
+                        Some(s) if *s == side[curr] => {
+                            return Err(CycleError {
+                                cycle: Self::odd_cycle(curr, next, &parent),
+                            });
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+
+        let (left, right): (HashMap<_, _>, HashMap<_, _>) =
+            side.into_iter().partition(|(_, s)| !*s);
+
+        Ok((left.into_keys().collect(), right.into_keys().collect()))
+    }
+
+    /// This function finds a maximum matching of a bipartite graph using the Hopcroft-Karp algorithm, in
+    /// `O(E * sqrt(V))` time. A matching is a set of edges with no node in common, and a maximum matching
+    /// has as many edges as possible. The direction of edges is ignored, and the two sides are found with
+    /// `bipartition`.
+    ///
+    /// The function returns the matched pairs `(u, v)` as a sorted `Vec<(&N, &N)>`, where there is an
+    /// edge from `u` to `v`. If there are edges both ways, `u` is on the first side of `bipartition`.
+    /// A `CycleError` holding an odd cycle is returned if the graph isn't bipartite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Ana", "Ben", "Cy", "Cleaning", "Driving", "Welding");
+    /// g.add_edge(&"Ana", &"Cleaning", None);
+    /// g.add_edge(&"Ana", &"Driving", None);
+    /// g.add_edge(&"Ben", &"Driving", None);
+    /// g.add_edge(&"Cy", &"Driving", None);
+    ///
+    /// let matching = g.maximum_bipartite_matching().unwrap();
+    ///
+    /// assert_eq!(matching.len(), 2);
+    /// assert!(matching.contains(&(&"Ana", &"Cleaning")));
+    /// ```
+    pub fn maximum_bipartite_matching(&self) -> Result<Vec<(&N, &N)>, CycleError<'_, N>> {
+        let (left, right) = self.bipartition()?;
+        let adjacent = self.undirected_adjacency();
+
+        let left: Vec<&N> = left.into_iter().collect();
+        let right: Vec<&N> = right.into_iter().collect();
+        let right_index: HashMap<&N, usize> =
+            right.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+
+        let neighbours: Vec<Vec<usize>> = left
+            .iter()
+            .map(|u| adjacent[u].iter().map(|v| right_index[v]).collect())
+            .collect();
+
+        let mut mate_left: Vec<Option<usize>> = vec![None; left.len()];
+        let mut mate_right: Vec<Option<usize>> = vec![None; right.len()];
+
+        loop {
+            // Find the length of shortest augmenting paths, layering the left nodes by their distance from a
+            // free left node along alternating paths.
+            let mut dist = vec![usize::MAX; left.len()];
+            let mut queue = VecDeque::new();
+            let mut found = false;
+
+            for u in 0..left.len() {
+                if mate_left[u].is_none() {
+                    dist[u] = 0;
+                    queue.push_back(u);
+                }
+            }
+
+            while let Some(u) = queue.pop_front() {
+                for &v in neighbours[u].iter() {
+                    match mate_right[v] {
+                        None => found = true,
+                        Some(w) if dist[w] == usize::MAX => {
+                            dist[w] = dist[u] + 1;
+                            queue.push_back(w);
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+
+            if !found {
+                break;
+            }
+
+            // Find a maximal set of disjoint augmenting paths along the layers with an explicit stack.
+            // `next[u]` is the position of the first neighbour of `u` that hasn't been tried.
+            let mut next = vec![0; left.len()];
+
+            for root in 0..left.len() {
+                if mate_left[root].is_some() {
+                    continue;
+                }
+
+                let mut stack = vec![root];
+                let mut chosen: Vec<usize> = Vec::new();
+
+                while let Some(&u) = stack.last() {
+                    let Some(&v) = neighbours[u].get(next[u]) else {
+                        // Dead end, so `u` can be skipped for the rest of this phase.
+                        dist[u] = usize::MAX;
+                        stack.pop();
+
+                        if chosen.pop().is_some() {
+                            next[*stack.last().expect("Chosen nodes have a parent")] += 1;
+                        }
+
+                        continue;
+                    };
+
+                    match mate_right[v] {
+                        None => {
+                            chosen.push(v);
+
+                            for (u, v) in stack.iter().zip(chosen.iter()) {
+                                mate_left[*u] = Some(*v);
+                                mate_right[*v] = Some(*u);
+                            }
+
+                            break;
+                        }
+                        Some(w) if dist[w] == dist[u] + 1 => {
+                            chosen.push(v);
+                            stack.push(w);
+                        }
+                        Some(_) => next[u] += 1,
+                    }
+                }
+            }
+        }
+
+        let mut matching: Vec<(&N, &N)> = mate_left
+            .into_iter()
+            .enumerate()
+            .filter_map(|(u, v)| {
+                let (u, v) = (left[u], right[v?]);
+
+                if self.edges[u].iter().any(|(dst, _)| **dst == *v) {
+                    Some((u, v))
+                } else {
+                    Some((v, u))
+                }
+            })
+            .collect();
+
+        matching.sort();
+
+        Ok(matching)
+    }
+
+    /// Builds the odd cycle closed by the edge between `u` and `v`, which are on the same side of a
+    /// breadth first search tree given by `parent`.
+    fn odd_cycle<'a>(u: &'a N, v: &'a N, parent: &HashMap<&'a N, &'a N>) -> Vec<&'a N> {
+        let ancestors = |mut n: &'a N| {
+            let mut path = vec![n];
+
+            while let Some(p) = parent.get(n) {
+                path.push(p);
+                n = p;
+            }
+
+            path
+        };
+
+        let (mut from_u, mut from_v) = (ancestors(u), ancestors(v));
+
+        // Both paths end at the root of the tree, so drop their common tail except for the lowest
+        // common ancestor.
+        while from_u.len() > 1
+            && from_v.len() > 1
+            && from_u[from_u.len() - 2] == from_v[from_v.len() - 2]
+        {
+            from_u.pop();
+            from_v.pop();
+        }
+
+        from_v.pop();
+        from_u.extend(from_v.into_iter().rev());
+        from_u.push(u);
+
+        from_u
+    }
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord + Measure + Sub<Output = E>,
+{
+    /// This function assigns nodes in `left` to nodes in `right` using the Hungarian algorithm, finding a
+    /// matching with as many pairs as possible and, out of those, the smallest total weight. A perfect
+    /// matching is found whenever one exists.
+    ///
+    /// A node in `left` can be paired with a node in `right` if there is an edge between them in either
+    /// direction, and `None` edges are treated as forbidden pairs. If there are several edges between a pair,
+    /// the lightest is used. Edges between nodes on the same side are ignored, and weights may be negative.
+    ///
+    /// The function returns an `Assignment`, holding the matched pairs and their total weight.
+    ///
+    /// Returned errors are:
+    /// - `GraphError::NodeNotFound` if a node in `left` or `right` doesn't exist.
+    /// - `GraphError::DuplicateNode` if a node appears twice in `left` or `right`, or in both of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, u32> = graph_with_nodes!("Ana", "Ben", "Cy", "Pipes", "Wires", "Walls");
+    /// g.add_edge(&"Ana", &"Pipes", Some(4));
+    /// g.add_edge(&"Ana", &"Wires", Some(1));
+    /// g.add_edge(&"Ana", &"Walls", Some(3));
+    /// g.add_edge(&"Ben", &"Pipes", Some(2));
+    /// g.add_edge(&"Ben", &"Walls", None);
+    /// g.add_edge(&"Cy", &"Pipes", Some(3));
+    /// g.add_edge(&"Cy", &"Wires", Some(2));
+    /// g.add_edge(&"Cy", &"Walls", Some(2));
+    ///
+    /// let res = g.min_weight_assignment(&[&"Ana", &"Ben", &"Cy"], &[&"Pipes", &"Wires", &"Walls"]).unwrap();
+    ///
+    /// assert_eq!(res.weight, 5);
+    /// assert_eq!(
+    ///     res.pairs,
+    ///     vec![(&"Ana", &"Wires", &Some(1)), (&"Ben", &"Pipes", &Some(2)), (&"Cy", &"Walls", &Some(2))]
+    /// );
+    /// ```
+    pub fn min_weight_assignment<'a>(
+        &'a self,
+        left: &[&'a N],
+        right: &[&'a N],
+    ) -> Result<Assignment<'a, N, E>, GraphError<'a, N>> {
+        let mut index: HashMap<&N, usize> = HashMap::new();
+
+        for (i, n) in left.iter().chain(right.iter()).enumerate() {
+            let Some(rc) = self.nodes.get(*n) else {
+                return Err(GraphError::NodeNotFound(n));
+            };
+
+            if index.insert(&**rc, i).is_some() {
+                return Err(GraphError::DuplicateNode(n));
+            }
+        }
+
+        let size = left.len() + right.len();
+        let (s, t) = (size, size + 1);
+        let is_left = |i: usize| i < left.len();
+
+        // Keep the lightest weighted edge between each pair of nodes on opposite sides.
+        let mut lightest: HashMap<(usize, usize), (&N, &N, &Option<E>)> = HashMap::new();
+
+        for (src, set) in self.edges.iter() {
+            for (dst, e) in set.iter() {
+                let (Some(&u), Some(&v), Some(w)) = (index.get(&**src), index.get(&**dst), e)
+                else {
+                    continue;
+                };
+
+                if is_left(u) == is_left(v) {
+                    continue;
+                }
+
+                let (l, r) = if is_left(u) { (u, v) } else { (v, u) };
+
+                lightest
+                    .entry((l, r))
+                    .and_modify(|curr| {
+                        if curr.2.as_ref().is_some_and(|c| w < c) {
+                            *curr = (&**src, &**dst, e);
+                        }
+                    })
+                    .or_insert((&**src, &**dst, e));
+            }
+        }
+
+        // Build a network from `s` through the left nodes and the right nodes to `t`, where each arc `a`
+        // has a reverse arc `a ^ 1`, and every arc can carry one unit. Even arcs have `cost` and odd arcs
+        // have its negation.
+        let mut adjacent: Vec<Vec<usize>> = vec![Vec::new(); size + 2];
+        let mut to: Vec<usize> = Vec::new();
+        let mut open: Vec<bool> = Vec::new();
+        let mut cost: Vec<E> = Vec::new();
+        let mut pairs = Vec::new();
+
+        let mut add_arc = |u: usize, v: usize, c: E| {
+            adjacent[u].push(to.len());
+            adjacent[v].push(to.len() + 1);
+            to.extend([v, u]);
+            open.extend([true, false]);
+            cost.push(c);
+        };
+
+        for u in 0..left.len() {
+            add_arc(s, u, E::zero());
+        }
+
+        for v in left.len()..size {
+            add_arc(v, t, E::zero());
+        }
+
+        let mut sorted: Vec<_> = lightest.into_iter().collect();
+        sorted.sort();
+
+        for ((l, r), edge) in sorted {
+            add_arc(l, r, edge.2.clone().expect("Unweighted edges were skipped"));
+            pairs.push(edge);
+        }
+
+        // Potentials keep the reduced cost of every open arc non-negative, so that Djikstra's algorithm can
+        // find the cheapest augmenting path. Each right node starts at its lightest edge, and `t` below them.
+        let mut potential = vec![E::zero(); size + 2];
+        let mut lowest: Vec<Option<E>> = vec![None; size + 2];
+
+        for (i, c) in cost.iter().enumerate().skip(size) {
+            let r = to[2 * i];
+
+            if lowest[r].as_ref().is_none_or(|curr| c < curr) {
+                lowest[r] = Some(c.clone());
+            }
+        }
+
+        // Right nodes without any edges are never reached, so their potential doesn't matter.
+        for (r, c) in lowest.iter().enumerate() {
+            if let Some(c) = c {
+                potential[r] = c.clone();
+            }
+        }
+
+        if let Some(min) = lowest.into_iter().flatten().min() {
+            potential[t] = min;
+        }
+
+        loop {
+            let reduced = |a: usize, potential: &[E]| {
+                // The tail and head of the even arc in the pair.
+                let (u, v) = (to[a | 1], to[a & !1]);
+                let forward = cost[a / 2].saturating_add(&potential[u]);
+
+                if a & 1 == 0 {
+                    forward - potential[v].clone()
+                } else {
+                    potential[v].clone() - forward
+                }
+            };
+
+            let mut dist: Vec<Option<E>> = vec![None; size + 2];
+            let mut via: Vec<Option<usize>> = vec![None; size + 2];
+            let mut pq = BinaryHeap::from([Reverse((E::zero(), s))]);
+            dist[s] = Some(E::zero());
+
+            while let Some(Reverse((d, u))) = pq.pop() {
+                if dist[u].as_ref() != Some(&d) {
+                    continue;
+                }
+
+                for &a in adjacent[u].iter() {
+                    if !open[a] {
+                        continue;
+                    }
+
+                    let v = to[a];
+                    let next = d.saturating_add(&reduced(a, &potential));
+
+                    if dist[v].as_ref().is_none_or(|curr| next < *curr) {
+                        dist[v] = Some(next.clone());
+                        via[v] = Some(a);
+                        pq.push(Reverse((next, v)));
+                    }
+                }
+            }
+
+            if dist[t].is_none() {
+                break;
+            }
+
+            for (u, d) in dist.into_iter().enumerate() {
+                if let Some(d) = d {
+                    potential[u] = potential[u].saturating_add(&d);
+                }
+            }
+
+            let mut curr = t;
+
+            while let Some(a) = via[curr] {
+                open[a] = false;
+                open[a ^ 1] = true;
+                curr = to[a ^ 1];
+            }
+        }
+
+        let mut matched: Vec<(&N, &N, &Option<E>)> = Vec::new();
+        let mut weight = E::zero();
+
+        for (i, edge) in pairs.into_iter().enumerate() {
+            if !open[2 * (size + i)] {
+                let (u, v, e) = edge;
+                let (l, r) = if is_left(index[u]) { (u, v) } else { (v, u) };
+
+                weight = weight.saturating_add(&e.clone().expect("Unweighted edges were skipped"));
+                matched.push((l, r, e));
+            }
+        }
+
+        matched.sort_by_key(|(l, _, _)| index[l]);
+
+        Ok(Assignment {
+            pairs: matched,
+            weight,
+        })
+    }
+
+    /// This function finds a matching of minimum total weight in a bipartite graph, out of the matchings with
+    /// as many pairs as possible. The two sides are found with `bipartition`, and the matching is found with
+    /// `min_weight_assignment`.
+    ///
+    /// A `CycleError` holding an odd cycle is returned if the graph isn't bipartite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4);
+    /// g.add_undirected_edge(&1, &2, Some(5));
+    /// g.add_undirected_edge(&2, &3, Some(1));
+    /// g.add_undirected_edge(&3, &4, Some(5));
+    ///
+    /// let res = g.min_weight_bipartite_matching().unwrap();
+    ///
+    /// assert_eq!(res.weight, 10);
+    /// assert_eq!(res.pairs.len(), 2);
+    /// ```
+    pub fn min_weight_bipartite_matching(&self) -> Result<Assignment<'_, N, E>, CycleError<'_, N>> {
+        let (left, right) = self.bipartition()?;
+        let left: Vec<&N> = left.into_iter().collect();
+        let right: Vec<&N> = right.into_iter().collect();
+
+        Ok(self
+            .min_weight_assignment(&left, &right)
+            .expect("Both sides of the bipartition are nodes"))
+    }
+}
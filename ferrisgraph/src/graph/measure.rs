@@ -0,0 +1,120 @@
+use super::OrderedFloat;
+use std::time::Duration;
+
+/// A type that can be used to measure the length of paths, such as the edge weights given to `djikstra`.
+///
+/// `Measure` is implemented for all of the integer types, `Duration`, and `OrderedFloat`. Other weight
+/// types can be used by implementing it.
+///
+/// # Examples
+///
+/// ```
+/// use ferrisgraph::graph::Measure;
+/// use std::time::Duration;
+///
+/// assert_eq!(i32::zero(), 0);
+/// assert_eq!(u8::default_weight(), 1);
+/// assert_eq!(Measure::saturating_add(&250u8, &10), 255);
+/// assert_eq!(Measure::checked_add(&250u8, &10), None);
+/// assert_eq!(Measure::checked_sub(&-100i8, &28), Some(-128));
+/// assert_eq!(Duration::default_weight(), Duration::from_secs(1));
+/// ```
+pub trait Measure: Ord + Clone {
+    /// The length of a path with no edges.
+    fn zero() -> Self;
+
+    /// The weight used for unweighted (`None`) edges.
+    fn default_weight() -> Self;
+
+    /// Adds two measures, staying at the largest or smallest value of the type instead of overflowing.
+    fn saturating_add(&self, other: &Self) -> Self;
+
+    /// Adds two measures, returning `None` if the result doesn't fit in the type.
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    /// Subtracts `other` from this measure, returning `None` if the result doesn't fit in the type.
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_measure_for_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Measure for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn default_weight() -> Self {
+                    1
+                }
+
+                fn saturating_add(&self, other: &Self) -> Self {
+                    <$t>::saturating_add(*self, *other)
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+impl_measure_for_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_measure_for_float {
+    ($($f:ty),*) => {
+        $(
+            impl Measure for OrderedFloat<$f> {
+                fn zero() -> Self {
+                    OrderedFloat(0.0)
+                }
+
+                fn default_weight() -> Self {
+                    OrderedFloat(1.0)
+                }
+
+                // Floating point addition already saturates at infinity.
+                fn saturating_add(&self, other: &Self) -> Self {
+                    OrderedFloat(self.0 + other.0)
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    Some(OrderedFloat(self.0 + other.0))
+                }
+
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    Some(OrderedFloat(self.0 - other.0))
+                }
+            }
+        )*
+    };
+}
+
+impl_measure_for_float!(f32, f64);
+
+impl Measure for Duration {
+    fn zero() -> Self {
+        Duration::ZERO
+    }
+
+    fn default_weight() -> Self {
+        Duration::from_secs(1)
+    }
+
+    fn saturating_add(&self, other: &Self) -> Self {
+        Duration::saturating_add(*self, *other)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Duration::checked_add(*self, *other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Duration::checked_sub(*self, *other)
+    }
+}
@@ -1,8 +1,27 @@
 pub mod algos;
+pub mod centrality;
+pub mod cliques;
+pub mod clustering;
+pub mod coloring;
+pub mod community;
+pub mod connectivity;
+pub mod dominators;
+pub mod euler;
+pub mod float;
+pub mod flow;
+#[allow(clippy::module_inception)]
 pub mod graph;
+pub mod matching;
+pub mod measure;
+pub mod pagerank;
+pub mod paths;
 pub mod queries;
+pub mod spanning;
+pub mod tsp;
 
 #[macro_use]
 pub mod macros;
 
+pub use float::OrderedFloat;
 pub use graph::Graph;
+pub use measure::Measure;
@@ -0,0 +1,218 @@
+use super::graph::GraphError;
+use super::Graph;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord,
+{
+    /// This function ranks the nodes of the graph using PageRank. A random surfer follows an outgoing edge
+    /// chosen uniformly at random with probability `damping`, and otherwise jumps to a node chosen uniformly
+    /// at random. A node's rank is the long run probability of the surfer being there, so the ranks add up
+    /// to `1`. The surfer always jumps from dangling nodes, i.e. nodes without any outgoing edges.
+    ///
+    /// `damping` should be between `0` and `1`, and is usually `0.85`. The ranks are updated until the total
+    /// change across all nodes in one iteration is below `tolerance`, or `max_iter` iterations have been run.
+    /// Parallel edges with different weights each count as a separate edge.
+    ///
+    /// The function returns a `HashMap<&N, f64>` mapping each node to its rank.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Home", "Setup", "Guides", "FAQ");
+    /// g.add_edge(&"Home", &"Setup", None);
+    /// g.add_edge(&"Home", &"Guides", None);
+    /// g.add_edge(&"Setup", &"Home", None);
+    /// g.add_edge(&"Guides", &"Home", None);
+    /// g.add_edge(&"FAQ", &"Home", None);
+    ///
+    /// let ranks = g.pagerank(0.85, 1e-10, 100);
+    ///
+    /// assert!(ranks[&"Home"] > ranks[&"Setup"]);
+    /// assert!((ranks[&"Setup"] - ranks[&"Guides"]).abs() < 1e-9);
+    /// assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    /// ```
+    pub fn pagerank(&self, damping: f64, tolerance: f64, max_iter: usize) -> HashMap<&N, f64> {
+        self.weighted_pagerank(damping, tolerance, max_iter, |_| 1.0)
+    }
+
+    /// This function ranks the nodes of the graph using PageRank, in the same way as `pagerank`, except that
+    /// the surfer follows each outgoing edge with probability proportional to `weight(e)`. Negative weights
+    /// are treated as `0`, and nodes whose outgoing edges all have a weight of `0` are dangling.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, u32> = graph_with_nodes!("Home", "Setup", "Guides");
+    /// g.add_edge(&"Home", &"Setup", Some(3));
+    /// g.add_edge(&"Home", &"Guides", Some(1));
+    /// g.add_edge(&"Setup", &"Home", None);
+    /// g.add_edge(&"Guides", &"Home", None);
+    ///
+    /// let ranks = g.weighted_pagerank(0.85, 1e-10, 100, |e| e.map_or(1.0, f64::from));
+    ///
+    /// assert!(ranks[&"Setup"] > ranks[&"Guides"]);
+    /// ```
+    pub fn weighted_pagerank<W>(
+        &self,
+        damping: f64,
+        tolerance: f64,
+        max_iter: usize,
+        weight: W,
+    ) -> HashMap<&N, f64>
+    where
+        W: Fn(&Option<E>) -> f64,
+    {
+        let n = self.nodes.len();
+
+        self.pagerank_with(
+            damping,
+            tolerance,
+            max_iter,
+            vec![1.0 / n as f64; n],
+            weight,
+        )
+    }
+
+    /// This function ranks the nodes of the graph using personalized PageRank. It is the same as
+    /// `weighted_pagerank`, except that the surfer jumps to a node chosen according to `restart`, both when
+    /// jumping at random and when leaving a dangling node. This ranks nodes by how close they are to the
+    /// nodes in `restart`. Use `|_| 1.0` as the `weight` to treat every edge equally.
+    ///
+    /// `restart` maps nodes to the relative chance of jumping to them, and doesn't need to add up to `1`.
+    /// Nodes missing from it are never jumped to, and negative values are treated as `0`. If every value is
+    /// `0`, nodes are jumped to uniformly at random, as in `pagerank`.
+    ///
+    /// `GraphError::NodeNotFound` is returned if a node in `restart` doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Rust", "Cargo", "Python", "Pip");
+    /// g.add_undirected_edge(&"Rust", &"Cargo", None);
+    /// g.add_undirected_edge(&"Python", &"Pip", None);
+    /// g.add_undirected_edge(&"Cargo", &"Pip", None);
+    ///
+    /// let restart = HashMap::from([(&"Rust", 1.0)]);
+    /// let ranks = g.personalized_pagerank(0.85, 1e-10, 100, &restart, |_| 1.0).unwrap();
+    ///
+    /// assert!(ranks[&"Cargo"] > ranks[&"Pip"]);
+    /// assert!(ranks[&"Rust"] > ranks[&"Python"]);
+    /// ```
+    pub fn personalized_pagerank<'a, W>(
+        &'a self,
+        damping: f64,
+        tolerance: f64,
+        max_iter: usize,
+        restart: &HashMap<&'a N, f64>,
+        weight: W,
+    ) -> Result<HashMap<&'a N, f64>, GraphError<'a, N>>
+    where
+        W: Fn(&Option<E>) -> f64,
+    {
+        let n = self.nodes.len();
+        let index: HashMap<&N, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (&**n, i))
+            .collect();
+        let mut jump = vec![0.0; n];
+
+        for (node, chance) in restart.iter() {
+            match index.get(node) {
+                Some(i) => jump[*i] = chance.max(0.0),
+                None => return Err(GraphError::NodeNotFound(node)),
+            }
+        }
+
+        let total: f64 = jump.iter().sum();
+
+        if total > 0.0 {
+            jump.iter_mut().for_each(|j| *j /= total);
+        } else {
+            jump = vec![1.0 / n as f64; n];
+        }
+
+        Ok(self.pagerank_with(damping, tolerance, max_iter, jump, weight))
+    }
+
+    /// Runs power iteration on the transition probabilities given by `weight`, jumping according to `jump`,
+    /// which must add up to `1`.
+    fn pagerank_with<W>(
+        &self,
+        damping: f64,
+        tolerance: f64,
+        max_iter: usize,
+        jump: Vec<f64>,
+        weight: W,
+    ) -> HashMap<&N, f64>
+    where
+        W: Fn(&Option<E>) -> f64,
+    {
+        let nodes: Vec<&N> = self.nodes.iter().map(|n| &**n).collect();
+        let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let n = nodes.len();
+
+        // The probability of following each outgoing edge, which is empty for dangling nodes.
+        let transitions: Vec<Vec<(usize, f64)>> = self
+            .edges
+            .values()
+            .map(|set| {
+                let weights: Vec<(usize, f64)> = set
+                    .iter()
+                    .map(|(dst, e)| (index[&**dst], weight(e).max(0.0)))
+                    .collect();
+                let total: f64 = weights.iter().map(|(_, w)| w).sum();
+
+                if total > 0.0 {
+                    weights.into_iter().map(|(v, w)| (v, w / total)).collect()
+                } else {
+                    Vec::new()
+                }
+            })
+            .collect();
+
+        let mut rank = vec![1.0 / n as f64; n];
+
+        for _ in 0..max_iter {
+            let dangling: f64 = (0..n)
+                .filter(|u| transitions[*u].is_empty())
+                .map(|u| rank[u])
+                .sum();
+
+            let jumped = 1.0 - damping + damping * dangling;
+            let mut next: Vec<f64> = jump.iter().map(|j| jumped * j).collect();
+
+            for (u, edges) in transitions.iter().enumerate() {
+                for (v, p) in edges.iter() {
+                    next[*v] += damping * rank[u] * p;
+                }
+            }
+
+            let change: f64 = rank
+                .iter()
+                .zip(next.iter())
+                .map(|(a, b)| (a - b).abs())
+                .sum();
+            rank = next;
+
+            if change < tolerance {
+                break;
+            }
+        }
+
+        nodes.into_iter().zip(rank).collect()
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Deref;
+
+/// A path through a graph, found by one of the search or shortest path algorithms.
+///
+/// `nodes` holds the nodes from the start of the path to the end, and `edges` holds the edges
+/// `(src, dst, weight)` that were taken between them, so `edges.len() == nodes.len() - 1`.
+/// `cost` is the total cost of the path. Its type `C` is the edge type by default, but paths found
+/// by `bfs` count the number of edges instead.
+#[derive(Debug, PartialEq)]
+pub struct Path<'a, N, E, C = E> {
+    pub nodes: Vec<&'a N>,
+    pub edges: Vec<(&'a N, &'a N, &'a Option<E>)>,
+    pub cost: C,
+}
+
+impl<'a, N, E, C> Path<'a, N, E, C>
+where
+    N: Hash + Eq,
+{
+    /// Builds a path by walking back from `dst` along `via`, which maps each node to the node and
+    /// edge it was reached from.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn walk_back(
+        dst: &'a N,
+        via: &HashMap<&'a N, (&'a N, &'a Option<E>)>,
+        cost: C,
+    ) -> Self {
+        let mut nodes = vec![dst];
+        let mut edges = Vec::new();
+        let mut curr = dst;
+
+        while let Some((prev, weight)) = via.get(curr) {
+            nodes.push(prev);
+            edges.push((*prev, curr, *weight));
+            curr = prev;
+        }
+
+        nodes.reverse();
+        edges.reverse();
+
+        Path { nodes, edges, cost }
+    }
+}
+
+/// The result of `bfs`. It dereferences to the predecessor map `HashMap<&N, &N>`, where the
+/// source is its own predecessor.
+#[derive(Debug)]
+pub struct BfsTree<'a, N, E> {
+    pub(crate) pred: HashMap<&'a N, &'a N>,
+    pub(crate) via: HashMap<&'a N, (&'a N, &'a Option<E>)>,
+}
+
+impl<'a, N, E> BfsTree<'a, N, E>
+where
+    N: Hash + Eq + Debug,
+{
+    /// Returns the path from the source to `dst` with the fewest edges, or `None` if `dst`
+    /// was not reached. The cost of the path is its number of edges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Berlin", "Paris", "London", "Milan");
+    /// g.add_edge(&"Berlin", &"Paris", Some(10));
+    /// g.add_edge(&"Paris", &"London", None);
+    ///
+    /// let tree = g.bfs(&"Berlin").unwrap();
+    /// let path = tree.path_to(&"London").unwrap();
+    ///
+    /// assert_eq!(path.nodes, vec![&"Berlin", &"Paris", &"London"]);
+    /// assert_eq!(path.edges, vec![(&"Berlin", &"Paris", &Some(10)), (&"Paris", &"London", &None)]);
+    /// assert_eq!(path.cost, 2);
+    /// assert!(tree.path_to(&"Milan").is_none());
+    /// ```
+    pub fn path_to(&self, dst: &N) -> Option<Path<'a, N, E, usize>> {
+        let (dst, _) = self.pred.get_key_value(dst)?;
+        let mut path = Path::walk_back(*dst, &self.via, 0);
+        path.cost = path.edges.len();
+
+        Some(path)
+    }
+}
+
+impl<'a, N, E> Deref for BfsTree<'a, N, E> {
+    type Target = HashMap<&'a N, &'a N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pred
+    }
+}
+
+/// The result of a single source shortest path algorithm such as `djikstra` or `bellman_ford`.
+///
+/// `dist` maps each reachable node to its total distance from the source, and `pred` maps each
+/// reachable node to its predecessor, where the predecessor to the source is `None`.
+#[derive(Debug)]
+pub struct ShortestPaths<'a, N, E> {
+    pub dist: HashMap<&'a N, E>,
+    pub pred: HashMap<&'a N, Option<&'a N>>,
+    pub(crate) via: HashMap<&'a N, (&'a N, &'a Option<E>)>,
+}
+
+impl<'a, N, E> ShortestPaths<'a, N, E>
+where
+    N: Hash + Eq + Debug,
+    E: Clone,
+{
+    /// Returns the shortest path from the source to `dst`, or `None` if `dst` was not reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Sydney", "Melbourne", "Perth");
+    ///
+    /// g.add_undirected_edge(&"Sydney", &"Melbourne", Some(7));
+    /// g.add_undirected_edge(&"Melbourne", &"Perth", Some(5));
+    /// g.add_undirected_edge(&"Sydney", &"Perth", Some(15));
+    ///
+    /// let path = g.djikstra(&"Sydney").unwrap().path_to(&"Perth").unwrap();
+    ///
+    /// assert_eq!(path.nodes, vec![&"Sydney", &"Melbourne", &"Perth"]);
+    /// assert_eq!(path.edges[1], (&"Melbourne", &"Perth", &Some(5)));
+    /// assert_eq!(path.cost, 12);
+    /// ```
+    pub fn path_to(&self, dst: &N) -> Option<Path<'a, N, E>> {
+        let (dst, cost) = self.dist.get_key_value(dst)?;
+
+        Some(Path::walk_back(*dst, &self.via, cost.clone()))
+    }
+}
+
+/// The result of an all pairs shortest path algorithm such as `floyd_warshall` or `johnson`.
+///
+/// `dist[u][v]` is the length of the shortest path from `u` to `v`, and `next[u][v]` is the node
+/// after `u` on that path, where `next[u][u]` is `u`. Pairs of nodes with no path between them
+/// are missing from both maps.
+#[derive(Debug)]
+pub struct AllPairsShortestPaths<'a, N, E> {
+    pub dist: HashMap<&'a N, HashMap<&'a N, E>>,
+    pub next: HashMap<&'a N, HashMap<&'a N, &'a N>>,
+    #[allow(clippy::type_complexity)]
+    pub(crate) first_edge: HashMap<&'a N, HashMap<&'a N, &'a Option<E>>>,
+}
+
+impl<'a, N, E> AllPairsShortestPaths<'a, N, E>
+where
+    N: Hash + Eq + Debug,
+    E: Clone,
+{
+    /// Returns the shortest path from `src` to `dst`, or `None` if there is no such path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Sydney", "Melbourne", "Perth");
+    ///
+    /// g.add_undirected_edge(&"Sydney", &"Melbourne", Some(7));
+    /// g.add_undirected_edge(&"Melbourne", &"Perth", Some(5));
+    /// g.add_edge(&"Sydney", &"Perth", Some(15));
+    ///
+    /// let paths = g.floyd_warshall().unwrap();
+    ///
+    /// assert_eq!(paths.path(&"Perth", &"Sydney").unwrap().nodes, vec![&"Perth", &"Melbourne", &"Sydney"]);
+    /// assert_eq!(paths.path(&"Sydney", &"Perth").unwrap().cost, 12);
+    /// ```
+    pub fn path(&self, src: &N, dst: &N) -> Option<Path<'a, N, E>> {
+        let (src, row) = self.dist.get_key_value(src)?;
+        let (dst, cost) = row.get_key_value(dst)?;
+
+        let mut nodes = vec![*src];
+        let mut edges = Vec::new();
+        let mut curr = *src;
+
+        while curr != *dst {
+            let hop = self.next[curr][dst];
+            edges.push((curr, hop, self.first_edge[curr][dst]));
+            nodes.push(hop);
+            curr = hop;
+        }
+
+        Some(Path {
+            nodes,
+            edges,
+            cost: cost.clone(),
+        })
+    }
+}
@@ -90,6 +90,7 @@ where
     /// assert_eq!(g.edges(&"Shanghai"), Ok(None));
     ///
     /// ```
+    #[allow(clippy::type_complexity)]
     pub fn edges<'a>(
         &self,
         node: &'a N,
@@ -164,7 +165,7 @@ where
     /// assert_eq!(g.num_edges(), 1);
     /// ```
     pub fn num_edges(&self) -> usize {
-        self.edges.iter().map(|(_, set)| set.len()).sum()
+        self.edges.values().map(|set| set.len()).sum()
     }
 
     /// This function returns the out-degree of the given node. That is, the number of outgoing edges.
@@ -222,8 +223,8 @@ where
         }
 
         self.edges
-            .iter()
-            .flat_map(|(_, set)| set.iter())
+            .values()
+            .flat_map(|set| set.iter())
             .filter(|(dst, _)| **dst == *node)
             .count()
     }
@@ -0,0 +1,166 @@
+use super::measure::Measure;
+use super::Graph;
+use crate::unionfind::UnionFind;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// A minimum spanning forest, found by `kruskal` or `prim`.
+///
+/// `edges` holds the undirected edges `(u, v, weight)` in the forest, where `u < v`, and `weight` is
+/// their total weight. A forest of a graph with `n` nodes and `c` connected components has `n - c` edges.
+#[derive(Debug, PartialEq)]
+pub struct SpanningForest<'a, N, E> {
+    pub edges: Vec<(&'a N, &'a N, &'a Option<E>)>,
+    pub weight: E,
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord + Measure,
+{
+    /// This function finds a minimum spanning forest of the graph, i.e. a minimum spanning tree of each
+    /// of its connected components. It is equivalent to `kruskal`.
+    ///
+    /// The graph is treated as undirected: the two directed edges added by `add_undirected_edge` count as
+    /// a single edge, and an edge added in only one direction can be used either way. Loops are ignored.
+    /// Unweighted edges have a weight of `Measure::default_weight`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Depot", "North", "South", "East", "Annex");
+    /// g.add_undirected_edge(&"Depot", &"North", Some(4));
+    /// g.add_undirected_edge(&"Depot", &"South", Some(3));
+    /// g.add_undirected_edge(&"North", &"South", Some(1));
+    /// g.add_undirected_edge(&"South", &"East", Some(6));
+    /// g.add_undirected_edge(&"North", &"East", Some(5));
+    ///
+    /// let forest = g.minimum_spanning_forest();
+    ///
+    /// assert_eq!(forest.weight, 9);
+    /// assert_eq!(forest.edges.len(), 3);
+    /// assert!(forest.edges.contains(&(&"North", &"South", &Some(1))));
+    /// assert!(forest.edges.contains(&(&"East", &"North", &Some(5))));
+    /// ```
+    pub fn minimum_spanning_forest(&self) -> SpanningForest<'_, N, E> {
+        self.kruskal()
+    }
+
+    /// This function finds a minimum spanning forest of the graph using Kruskal's algorithm, which adds
+    /// the lightest edges first, using a `UnionFind` to skip edges that would form a cycle.
+    ///
+    /// The graph is treated as undirected, in the same way as `minimum_spanning_forest`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4);
+    /// g.add_undirected_edge(&1, &2, Some(2));
+    /// g.add_undirected_edge(&2, &3, Some(2));
+    /// g.add_undirected_edge(&1, &3, Some(3));
+    ///
+    /// let forest = g.kruskal();
+    ///
+    /// assert_eq!(forest.weight, 4);
+    /// assert_eq!(forest.edges, vec![(&1, &2, &Some(2)), (&2, &3, &Some(2))]);
+    /// ```
+    pub fn kruskal(&self) -> SpanningForest<'_, N, E> {
+        let mut candidates: Vec<_> = self
+            .undirected_edges()
+            .into_iter()
+            .map(|(u, v, e)| (e.clone().unwrap_or_else(E::default_weight), (u, v, e)))
+            .collect();
+
+        candidates.sort();
+
+        let mut sets = UnionFind::new();
+
+        for n in self.nodes.iter() {
+            sets.make_set(&**n);
+        }
+
+        let mut edges = Vec::new();
+        let mut weight = E::zero();
+
+        for (w, (u, v, e)) in candidates {
+            if sets.union(&u, &v) {
+                weight = weight.saturating_add(&w);
+                edges.push((u, v, e));
+            }
+        }
+
+        SpanningForest { edges, weight }
+    }
+
+    /// This function finds a minimum spanning forest of the graph using Prim's algorithm, which grows
+    /// a tree from one node of each connected component, always adding the lightest edge leaving it.
+    ///
+    /// The graph is treated as undirected, in the same way as `minimum_spanning_forest`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4, 5);
+    /// g.add_undirected_edge(&1, &2, Some(2));
+    /// g.add_undirected_edge(&2, &3, Some(2));
+    /// g.add_undirected_edge(&1, &3, Some(3));
+    /// g.add_edge(&5, &4, None);
+    ///
+    /// let forest = g.prim();
+    ///
+    /// assert_eq!(forest.weight, 5);
+    /// assert_eq!(forest.edges, vec![(&1, &2, &Some(2)), (&2, &3, &Some(2)), (&4, &5, &None)]);
+    /// ```
+    pub fn prim(&self) -> SpanningForest<'_, N, E> {
+        let mut adjacent: HashMap<&N, Vec<(&N, &Option<E>)>> = HashMap::new();
+
+        for (u, v, e) in self.undirected_edges() {
+            adjacent.entry(u).or_default().push((v, e));
+            adjacent.entry(v).or_default().push((u, e));
+        }
+
+        let mut visited: HashSet<&N> = HashSet::new();
+        let mut edges = Vec::new();
+        let mut weight = E::zero();
+
+        for root in self.nodes.iter() {
+            if !visited.insert(&**root) {
+                continue;
+            }
+
+            let mut pq = BinaryHeap::new();
+
+            for (v, e) in adjacent.get(&**root).into_iter().flatten() {
+                let w = (*e).clone().unwrap_or_else(E::default_weight);
+                pq.push(Reverse((w, &**root, *v, *e)));
+            }
+
+            while let Some(Reverse((w, u, v, e))) = pq.pop() {
+                if !visited.insert(v) {
+                    continue;
+                }
+
+                weight = weight.saturating_add(&w);
+                edges.push(if u < v { (u, v, e) } else { (v, u, e) });
+
+                for (next, e) in adjacent.get(v).into_iter().flatten() {
+                    if !visited.contains(next) {
+                        let w = (*e).clone().unwrap_or_else(E::default_weight);
+                        pq.push(Reverse((w, v, *next, *e)));
+                    }
+                }
+            }
+        }
+
+        SpanningForest { edges, weight }
+    }
+}
@@ -0,0 +1,567 @@
+use super::graph::GraphError;
+use super::measure::Measure;
+use super::Graph;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// The largest number of odd degree nodes for which `christofides` finds an exact minimum weight matching.
+/// With more, the nodes are matched greedily, and the tour may cost more than 1.5 times the shortest tour.
+pub const EXACT_MATCHING_LIMIT: usize = 20;
+
+/// The largest number of nodes that `held_karp` accepts, since its time and memory grow exponentially.
+/// Its tables hold `2^(V - 1) * (V - 1)` optional weights and as many one byte indices, which is about ten
+/// million of each at this limit, or about 170 MB with `i64` weights.
+pub const HELD_KARP_LIMIT: usize = 20;
+
+/// A tour of the graph, found by one of the travelling salesman algorithms.
+///
+/// `nodes` visits every node of the graph exactly once and then returns to the start, e.g. `[a, b, c, a]`,
+/// so `nodes.len()` is one more than the number of nodes in the graph. A graph with a single node has the
+/// tour `[a]`. `cost` is the total weight of the edges along the tour.
+#[derive(Debug, PartialEq)]
+pub struct Tour<'a, N, E> {
+    pub nodes: Vec<&'a N>,
+    pub cost: E,
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Hash + Eq + Ord + Debug,
+    E: Hash + Eq + Ord + Measure,
+{
+    /// This function builds a tour starting at `start` using the nearest neighbour heuristic, which always
+    /// moves to the closest node that hasn't been visited yet. It runs in `O(V^2)` time, and its tours can
+    /// usually be shortened a lot with `two_opt`.
+    ///
+    /// The weight of going from one node to another is the lightest edge between them, where unweighted edges
+    /// have a weight of `Measure::default_weight`. The function returns `None` if it gets stuck at a node with
+    /// no edges to unvisited nodes, or with no edge back to `start`, even if another tour exists.
+    /// `GraphError::NodeNotFound` is returned if `start` doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Depot", "Bakery", "Cafe", "Deli");
+    /// g.add_undirected_edge(&"Depot", &"Bakery", Some(1));
+    /// g.add_undirected_edge(&"Depot", &"Cafe", Some(4));
+    /// g.add_undirected_edge(&"Depot", &"Deli", Some(3));
+    /// g.add_undirected_edge(&"Bakery", &"Cafe", Some(2));
+    /// g.add_undirected_edge(&"Bakery", &"Deli", Some(5));
+    /// g.add_undirected_edge(&"Cafe", &"Deli", Some(6));
+    ///
+    /// let tour = g.nearest_neighbour_tour(&"Depot").unwrap().unwrap();
+    ///
+    /// assert_eq!(tour.nodes, vec![&"Depot", &"Bakery", &"Cafe", &"Deli", &"Depot"]);
+    /// assert_eq!(tour.cost, 12);
+    /// ```
+    pub fn nearest_neighbour_tour<'a>(
+        &'a self,
+        start: &'a N,
+    ) -> Result<Option<Tour<'a, N, E>>, GraphError<'a, N>> {
+        let (nodes, dist) = self.distance_matrix(false);
+        let s = Self::tour_start(&nodes, start)?;
+
+        let mut order = vec![s];
+        let mut visited = vec![false; nodes.len()];
+        visited[s] = true;
+
+        while order.len() < nodes.len() {
+            let curr = order[order.len() - 1];
+            let closest = (0..nodes.len())
+                .filter(|v| !visited[*v])
+                .filter_map(|v| dist[curr][v].clone().map(|w| (w, v)))
+                .min();
+
+            match closest {
+                Some((_, v)) => {
+                    visited[v] = true;
+                    order.push(v);
+                }
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Self::tour(&nodes, &dist, &order))
+    }
+
+    /// This function shortens a tour using the 2-opt heuristic, which repeatedly reverses a section of the
+    /// tour whenever that makes it cheaper, until no reversal helps. The tour keeps its starting node, and
+    /// only uses edges that exist, so it also works on directed graphs.
+    ///
+    /// `tour` must be a tour of this graph, such as one returned by `nearest_neighbour_tour`. Its cost is
+    /// worked out again from the graph, so the `cost` it was given with is ignored.
+    ///
+    /// Returned errors are:
+    /// - `GraphError::NodeNotFound` if a node in `tour` doesn't exist.
+    /// - `GraphError::DuplicateNode` if `tour` visits a node more than once before returning to its start.
+    /// - `GraphError::InvalidTour` if `tour` doesn't return to its start, misses a node, or uses an edge
+    ///   that doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::{Graph, tsp::Tour}, graph_with_nodes};
+    ///
+    /// let mut g: Graph<(i32, i32), i32> = graph_with_nodes!((0, 0), (0, 1), (1, 0), (1, 1));
+    ///
+    /// // Manhattan distances between the corners of a square
+    /// for u in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+    ///     for v in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+    ///         if u != v {
+    ///             g.add_edge(&u, &v, Some((u.0 - v.0).abs() + (u.1 - v.1).abs()));
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let crossing = Tour { nodes: vec![&(0, 0), &(1, 1), &(0, 1), &(1, 0), &(0, 0)], cost: 6 };
+    /// let tour = g.two_opt(crossing).unwrap();
+    ///
+    /// assert_eq!(tour.nodes, vec![&(0, 0), &(0, 1), &(1, 1), &(1, 0), &(0, 0)]);
+    /// assert_eq!(tour.cost, 4);
+    /// ```
+    pub fn two_opt<'a>(
+        &'a self,
+        tour: Tour<'a, N, E>,
+    ) -> Result<Tour<'a, N, E>, GraphError<'a, N>> {
+        let (nodes, dist) = self.distance_matrix(false);
+        let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+
+        // A tour of more than one node ends with its start, which is left out of `order`.
+        let visits = match tour.nodes.split_last() {
+            Some((last, visits)) if visits.first() == Some(last) => visits,
+            Some(_) if tour.nodes.len() == 1 => &tour.nodes[..],
+            _ => return Err(GraphError::InvalidTour),
+        };
+
+        let mut order: Vec<usize> = Vec::new();
+        let mut visited = vec![false; nodes.len()];
+
+        for n in visits.iter() {
+            let Some(i) = index.get(n) else {
+                return Err(GraphError::NodeNotFound(n));
+            };
+
+            if visited[*i] {
+                return Err(GraphError::DuplicateNode(n));
+            }
+
+            visited[*i] = true;
+            order.push(*i);
+        }
+
+        if order.len() < nodes.len() {
+            return Err(GraphError::InvalidTour);
+        }
+
+        let Some(given) = Self::tour(&nodes, &dist, &order) else {
+            return Err(GraphError::InvalidTour);
+        };
+
+        let n = order.len();
+
+        if n <= 2 {
+            return Ok(given);
+        }
+        let mut improved = true;
+
+        while improved {
+            improved = false;
+
+            for i in 1..n {
+                for j in i + 1..n {
+                    let (prev, next) = (order[i - 1], order[(j + 1) % n]);
+
+                    // Only the edges into, out of and within the reversed section change.
+                    let old = Self::sum(
+                        [dist[prev][order[i]].clone(), dist[order[j]][next].clone()]
+                            .into_iter()
+                            .chain((i..j).map(|k| dist[order[k]][order[k + 1]].clone())),
+                    );
+                    let new = Self::sum(
+                        [dist[prev][order[j]].clone(), dist[order[i]][next].clone()]
+                            .into_iter()
+                            .chain((i..j).map(|k| dist[order[k + 1]][order[k]].clone())),
+                    );
+
+                    if let (Some(old), Some(new)) = (old, new) {
+                        if new < old {
+                            order[i..=j].reverse();
+                            improved = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Self::tour(&nodes, &dist, &order).expect("Reversals only use edges that exist"))
+    }
+
+    /// This function builds a tour starting at `start` using the Christofides algorithm. It joins a minimum
+    /// spanning tree to a minimum weight perfect matching of the tree's odd degree nodes, walks an Eulerian
+    /// circuit of the result, and skips nodes that were already visited.
+    ///
+    /// The graph is treated as undirected, so the weight between two nodes is the lightest edge between them
+    /// in either direction, and there must be an edge between every pair of nodes. If the weights are metric,
+    /// i.e. they obey the triangle inequality, the tour costs at most 1.5 times as much as the shortest tour.
+    /// When the tree has more than `EXACT_MATCHING_LIMIT` odd degree nodes, a greedy matching is used
+    /// instead, and this bound no longer holds.
+    ///
+    /// The function returns `None` if a pair of nodes has no edge between them.
+    /// `GraphError::NodeNotFound` is returned if `start` doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Depot", "Bakery", "Cafe", "Deli");
+    /// g.add_undirected_edge(&"Depot", &"Bakery", Some(2));
+    /// g.add_undirected_edge(&"Depot", &"Cafe", Some(3));
+    /// g.add_undirected_edge(&"Depot", &"Deli", Some(2));
+    /// g.add_undirected_edge(&"Bakery", &"Cafe", Some(2));
+    /// g.add_undirected_edge(&"Bakery", &"Deli", Some(3));
+    /// g.add_undirected_edge(&"Cafe", &"Deli", Some(2));
+    ///
+    /// let tour = g.christofides(&"Depot").unwrap().unwrap();
+    ///
+    /// assert_eq!(tour.cost, 8);
+    /// assert_eq!(tour.nodes.len(), 5);
+    /// ```
+    pub fn christofides<'a>(
+        &'a self,
+        start: &'a N,
+    ) -> Result<Option<Tour<'a, N, E>>, GraphError<'a, N>> {
+        let (nodes, dist) = self.distance_matrix(true);
+        let s = Self::tour_start(&nodes, start)?;
+        let n = nodes.len();
+
+        if (0..n).any(|u| (0..n).any(|v| u != v && dist[u][v].is_none())) {
+            return Ok(None);
+        }
+
+        let weight = |u: usize, v: usize| dist[u][v].clone().expect("The graph is complete");
+
+        // Build a minimum spanning tree with Prim's algorithm, which is O(V^2) on a complete graph.
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut in_tree = vec![false; n];
+        let mut closest: Vec<Option<(E, usize)>> = vec![None; n];
+        closest[s] = Some((E::zero(), s));
+
+        for _ in 0..n {
+            let (_, u) = (0..n)
+                .filter(|u| !in_tree[*u])
+                .filter_map(|u| closest[u].clone().map(|(w, _)| (w, u)))
+                .min()
+                .expect("The graph is complete");
+
+            in_tree[u] = true;
+
+            if let Some((_, parent)) = closest[u] {
+                if parent != u {
+                    edges.push((parent, u));
+                }
+            }
+
+            for v in 0..n {
+                if !in_tree[v] && closest[v].as_ref().is_none_or(|(w, _)| weight(u, v) < *w) {
+                    closest[v] = Some((weight(u, v), u));
+                }
+            }
+        }
+
+        // Pair up the nodes with an odd degree in the tree, so that every degree becomes even.
+        let mut degree = vec![0; n];
+
+        for (u, v) in edges.iter() {
+            degree[*u] += 1;
+            degree[*v] += 1;
+        }
+
+        let odd: Vec<usize> = (0..n).filter(|u| degree[*u] % 2 == 1).collect();
+        edges.extend(Self::min_weight_perfect_matching(&odd, &weight));
+
+        // Walk an Eulerian circuit of the tree and matching with Hierholzer's algorithm, keeping the first
+        // visit to each node.
+        let mut adjacent: Vec<Vec<usize>> = vec![Vec::new(); n];
+
+        for (id, (u, v)) in edges.iter().enumerate() {
+            adjacent[*u].push(id);
+            adjacent[*v].push(id);
+        }
+
+        let mut used = vec![false; edges.len()];
+        let mut next = vec![0; n];
+        let mut stack = vec![s];
+        let mut circuit = Vec::new();
+
+        while let Some(&u) = stack.last() {
+            while next[u] < adjacent[u].len() && used[adjacent[u][next[u]]] {
+                next[u] += 1;
+            }
+
+            match adjacent[u].get(next[u]) {
+                Some(&id) => {
+                    used[id] = true;
+
+                    let (a, b) = edges[id];
+                    stack.push(if a == u { b } else { a });
+                }
+                None => {
+                    stack.pop();
+                    circuit.push(u);
+                }
+            }
+        }
+
+        let mut visited = vec![false; n];
+        let order: Vec<usize> = circuit
+            .into_iter()
+            .rev()
+            .filter(|u| !std::mem::replace(&mut visited[*u], true))
+            .collect();
+
+        Ok(Self::tour(&nodes, &dist, &order))
+    }
+
+    /// This function finds the shortest tour starting at `start` using the Held-Karp dynamic programming
+    /// algorithm. It runs in `O(2^V * V^2)` time and uses `O(2^V * V)` memory, so it only accepts graphs
+    /// with up to `HELD_KARP_LIMIT` nodes. Weights are the same as in `nearest_neighbour_tour`, and the graph
+    /// may be directed.
+    ///
+    /// The function returns `None` if there is no tour of the graph.
+    ///
+    /// Returned errors are:
+    /// - `GraphError::NodeNotFound` if `start` doesn't exist.
+    /// - `GraphError::TooManyNodes` if the graph has more than `HELD_KARP_LIMIT` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::{graph::Graph, graph_with_nodes};
+    ///
+    /// let mut g: Graph<&str, i32> = graph_with_nodes!("Depot", "Bakery", "Cafe", "Deli");
+    /// g.add_undirected_edge(&"Depot", &"Bakery", Some(1));
+    /// g.add_undirected_edge(&"Depot", &"Cafe", Some(4));
+    /// g.add_undirected_edge(&"Depot", &"Deli", Some(3));
+    /// g.add_undirected_edge(&"Bakery", &"Cafe", Some(2));
+    /// g.add_undirected_edge(&"Bakery", &"Deli", Some(5));
+    /// g.add_undirected_edge(&"Cafe", &"Deli", Some(6));
+    /// g.add_edge(&"Cafe", &"Deli", Some(1));
+    ///
+    /// let tour = g.held_karp(&"Depot").unwrap().unwrap();
+    ///
+    /// assert_eq!(tour.nodes, vec![&"Depot", &"Bakery", &"Cafe", &"Deli", &"Depot"]);
+    /// assert_eq!(tour.cost, 7);
+    /// ```
+    pub fn held_karp<'a>(
+        &'a self,
+        start: &'a N,
+    ) -> Result<Option<Tour<'a, N, E>>, GraphError<'a, N>> {
+        let (nodes, dist) = self.distance_matrix(false);
+        let s = Self::tour_start(&nodes, start)?;
+
+        if nodes.len() > HELD_KARP_LIMIT {
+            return Err(GraphError::TooManyNodes(nodes.len(), HELD_KARP_LIMIT));
+        }
+
+        // `cost[mask * m + j]` is the cost of the cheapest path from `s` through the nodes in `mask`, ending
+        // at `others[j]`, and `prev` holds the index in `others` of the node before `others[j]` on that path,
+        // which fits in a byte since there are fewer than `HELD_KARP_LIMIT` others.
+        let others: Vec<usize> = (0..nodes.len()).filter(|v| *v != s).collect();
+        let m = others.len();
+        let full = (1 << m) - 1;
+
+        let mut cost: Vec<Option<E>> = vec![None; (1 << m) * m];
+        let mut prev: Vec<u8> = vec![0; (1 << m) * m];
+
+        for (j, v) in others.iter().enumerate() {
+            cost[(1 << j) * m + j] = dist[s][*v].clone();
+        }
+
+        for mask in 1..=full {
+            for j in (0..m).filter(|j| mask & (1 << j) != 0) {
+                let Some(c) = cost[mask * m + j].clone() else {
+                    continue;
+                };
+
+                for k in (0..m).filter(|k| mask & (1 << k) == 0) {
+                    if let Some(w) = &dist[others[j]][others[k]] {
+                        let next = (mask | (1 << k)) * m + k;
+                        let total = c.saturating_add(w);
+
+                        if cost[next].as_ref().is_none_or(|curr| total < *curr) {
+                            cost[next] = Some(total);
+                            prev[next] = j as u8;
+                        }
+                    }
+                }
+            }
+        }
+
+        if m == 0 {
+            return Ok(Self::tour(&nodes, &dist, &[s]));
+        }
+
+        let last = (0..m)
+            .filter_map(|j| {
+                let c = cost[full * m + j].as_ref()?;
+                Some((c.saturating_add(dist[others[j]][s].as_ref()?), j))
+            })
+            .min();
+
+        let Some((_, mut j)) = last else {
+            return Ok(None);
+        };
+
+        let mut order = Vec::new();
+        let mut mask = full;
+
+        while mask != 0 {
+            order.push(others[j]);
+
+            let before = prev[mask * m + j] as usize;
+            mask &= !(1 << j);
+            j = before;
+        }
+
+        order.push(s);
+        order.reverse();
+
+        Ok(Self::tour(&nodes, &dist, &order))
+    }
+
+    /// Returns the nodes in order, along with a matrix of the lightest edge from each node to each other node.
+    /// If `undirected` is `true`, edges in either direction are used. Loops are left out.
+    #[allow(clippy::type_complexity)]
+    fn distance_matrix(&self, undirected: bool) -> (Vec<&N>, Vec<Vec<Option<E>>>) {
+        let nodes: Vec<&N> = self.nodes.iter().map(|n| &**n).collect();
+        let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let mut dist: Vec<Vec<Option<E>>> = vec![vec![None; nodes.len()]; nodes.len()];
+
+        for (src, set) in self.edges.iter() {
+            for (dst, e) in set.iter() {
+                let (u, v) = (index[&**src], index[&**dst]);
+                let w = e.clone().unwrap_or_else(E::default_weight);
+
+                if u == v {
+                    continue;
+                }
+
+                for (a, b) in [(u, v), (v, u)]
+                    .into_iter()
+                    .take(if undirected { 2 } else { 1 })
+                {
+                    if dist[a][b].as_ref().is_none_or(|curr| w < *curr) {
+                        dist[a][b] = Some(w.clone());
+                    }
+                }
+            }
+        }
+
+        (nodes, dist)
+    }
+
+    /// Finds the position of `start` in `nodes`.
+    fn tour_start<'a>(nodes: &[&'a N], start: &'a N) -> Result<usize, GraphError<'a, N>> {
+        nodes
+            .binary_search(&start)
+            .map_err(|_| GraphError::NodeNotFound(start))
+    }
+
+    /// Builds the tour visiting `order` and returning to its first node, or `None` if an edge is missing.
+    #[allow(clippy::type_complexity)]
+    fn tour<'a>(
+        nodes: &[&'a N],
+        dist: &[Vec<Option<E>>],
+        order: &[usize],
+    ) -> Option<Tour<'a, N, E>> {
+        let cost = if order.len() == 1 {
+            E::zero()
+        } else {
+            Self::sum(
+                (0..order.len()).map(|i| dist[order[i]][order[(i + 1) % order.len()]].clone()),
+            )?
+        };
+
+        let mut tour: Vec<&N> = order.iter().map(|u| nodes[*u]).collect();
+
+        if order.len() > 1 {
+            tour.push(nodes[order[0]]);
+        }
+
+        Some(Tour { nodes: tour, cost })
+    }
+
+    /// Adds up the given weights, or returns `None` if any of them is missing.
+    fn sum(mut weights: impl Iterator<Item = Option<E>>) -> Option<E> {
+        weights.try_fold(E::zero(), |total, w| Some(total.saturating_add(&w?)))
+    }
+
+    /// Pairs up an even number of nodes, minimising the total weight of the pairs. This is done exactly with
+    /// dynamic programming over subsets for up to `EXACT_MATCHING_LIMIT` nodes, and greedily otherwise.
+    fn min_weight_perfect_matching<W>(nodes: &[usize], weight: &W) -> Vec<(usize, usize)>
+    where
+        W: Fn(usize, usize) -> E,
+    {
+        let k = nodes.len();
+
+        if k > EXACT_MATCHING_LIMIT {
+            let mut pairs: Vec<(E, usize, usize)> = (0..k)
+                .flat_map(|i| (i + 1..k).map(move |j| (i, j)))
+                .map(|(i, j)| (weight(nodes[i], nodes[j]), i, j))
+                .collect();
+
+            pairs.sort();
+
+            let mut matched = vec![false; k];
+            let mut matching = Vec::new();
+
+            for (_, i, j) in pairs {
+                if !matched[i] && !matched[j] {
+                    matched[i] = true;
+                    matched[j] = true;
+                    matching.push((nodes[i], nodes[j]));
+                }
+            }
+
+            return matching;
+        }
+
+        // `best[mask]` is the cheapest way to pair up the nodes in `mask`, always pairing its lowest node.
+        let mut best: Vec<Option<(E, usize, usize)>> = vec![None; 1 << k];
+        best[0] = Some((E::zero(), 0, 0));
+
+        for mask in 0..(1usize << k) {
+            let Some((total, _, _)) = best[mask].clone() else {
+                continue;
+            };
+
+            let Some(i) = (0..k).find(|i| mask & (1 << i) == 0) else {
+                continue;
+            };
+
+            for j in (i + 1..k).filter(|j| mask & (1 << j) == 0) {
+                let next = mask | (1 << i) | (1 << j);
+                let total = total.saturating_add(&weight(nodes[i], nodes[j]));
+
+                if best[next].as_ref().is_none_or(|(curr, _, _)| total < *curr) {
+                    best[next] = Some((total, i, j));
+                }
+            }
+        }
+
+        let mut matching = Vec::new();
+        let mut mask = (1 << k) - 1;
+
+        while mask != 0 {
+            let (_, i, j) = best[mask].clone().expect("Every even subset can be paired");
+            matching.push((nodes[i], nodes[j]));
+            mask &= !((1 << i) | (1 << j));
+        }
+
+        matching
+    }
+}
@@ -1 +1,3 @@
 pub mod graph;
+pub mod random;
+pub mod unionfind;
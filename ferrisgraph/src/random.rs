@@ -0,0 +1,43 @@
+/// A small linear congruential generator, which always gives the same numbers for the same seed.
+///
+/// It is used by algorithms that make random choices, such as `Graph::label_propagation`, so that their
+/// results can be reproduced. The numbers are not suitable for anything that needs to be unpredictable.
+///
+/// # Examples
+///
+/// ```
+/// use ferrisgraph::random::Lcg;
+///
+/// let mut rng = Lcg::new(42);
+/// let mut same = Lcg::new(42);
+///
+/// let roll = rng.below(6);
+///
+/// assert!(roll < 6);
+/// assert_eq!(roll, same.below(6));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Lcg {
+    state: u64,
+}
+
+impl Lcg {
+    /// Creates a generator starting from `seed`.
+    pub fn new(seed: u64) -> Self {
+        Lcg { state: seed }
+    }
+
+    /// Steps the generator and returns a number below `m`, taken from its upper bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` is `0`.
+    pub fn below(&mut self, m: u64) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+
+        (self.state >> 33) % m
+    }
+}
@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A disjoint-set (union-find) data structure, which keeps track of elements split into sets.
+///
+/// Sets are merged using union by rank, and `find` compresses paths, so that every operation
+/// runs in close to constant time.
+///
+/// # Examples
+///
+/// ```
+/// use ferrisgraph::unionfind::UnionFind;
+///
+/// let mut uf: UnionFind<&str> = UnionFind::new();
+///
+/// uf.make_set("Tokyo");
+/// uf.make_set("Osaka");
+/// uf.make_set("Kyoto");
+///
+/// assert!(uf.union(&"Tokyo", &"Osaka"));
+/// assert_eq!(uf.union(&"Osaka", &"Tokyo"), false);
+///
+/// assert!(uf.connected(&"Tokyo", &"Osaka"));
+/// assert_eq!(uf.connected(&"Tokyo", &"Kyoto"), false);
+///
+/// assert_eq!(uf.num_sets(), 2);
+/// assert_eq!(uf.sets(), vec![vec![&"Tokyo", &"Osaka"], vec![&"Kyoto"]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct UnionFind<N> {
+    index: HashMap<N, usize>,
+    elements: Vec<N>,
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    size: Vec<usize>,
+    num_sets: usize,
+}
+
+impl<N> Default for UnionFind<N>
+where
+    N: Hash + Eq + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N> UnionFind<N>
+where
+    N: Hash + Eq + Clone,
+{
+    /// Creates an empty `UnionFind`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::unionfind::UnionFind;
+    /// let uf: UnionFind<i32> = UnionFind::new();
+    /// ```
+    pub fn new() -> Self {
+        UnionFind {
+            index: HashMap::new(),
+            elements: Vec::new(),
+            parent: Vec::new(),
+            rank: Vec::new(),
+            size: Vec::new(),
+            num_sets: 0,
+        }
+    }
+
+    /// Adds a new set containing only the given element.
+    /// Returns `true` if successful, and `false` if the element already exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::unionfind::UnionFind;
+    /// let mut uf: UnionFind<i32> = UnionFind::new();
+    ///
+    /// assert!(uf.make_set(1));
+    /// assert_eq!(uf.make_set(1), false);
+    /// ```
+    pub fn make_set(&mut self, element: N) -> bool {
+        if self.index.contains_key(&element) {
+            return false;
+        }
+
+        let i = self.elements.len();
+
+        self.index.insert(element.clone(), i);
+        self.elements.push(element);
+        self.parent.push(i);
+        self.rank.push(0);
+        self.size.push(1);
+        self.num_sets += 1;
+
+        true
+    }
+
+    /// Returns the representative of the set containing the given element, or `None` if the
+    /// element doesn't exist. Two elements are in the same set exactly when they have the same
+    /// representative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::unionfind::UnionFind;
+    /// let mut uf: UnionFind<i32> = UnionFind::new();
+    ///
+    /// uf.make_set(1);
+    /// uf.make_set(2);
+    ///
+    /// assert_eq!(uf.find(&1), Some(&1));
+    /// assert_eq!(uf.find(&3), None);
+    ///
+    /// uf.union(&1, &2);
+    /// assert_eq!(uf.find(&2), Some(&1));
+    /// ```
+    pub fn find(&mut self, element: &N) -> Option<&N> {
+        let i = *self.index.get(element)?;
+        let root = self.root(i);
+
+        Some(&self.elements[root])
+    }
+
+    /// Returns `true` if the two given elements exist and are in the same set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::unionfind::UnionFind;
+    /// let mut uf: UnionFind<i32> = UnionFind::new();
+    ///
+    /// uf.make_set(1);
+    /// uf.make_set(2);
+    /// assert_eq!(uf.connected(&1, &2), false);
+    ///
+    /// uf.union(&1, &2);
+    /// assert!(uf.connected(&1, &2));
+    /// assert_eq!(uf.connected(&1, &3), false);
+    /// ```
+    pub fn connected(&mut self, a: &N, b: &N) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(&a), Some(&b)) => self.root(a) == self.root(b),
+            _ => false,
+        }
+    }
+
+    /// Merges the sets containing the two given elements.
+    /// Returns `true` if successful, and `false` if the elements are already in the same set,
+    /// or if either element doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::unionfind::UnionFind;
+    /// let mut uf: UnionFind<i32> = UnionFind::new();
+    ///
+    /// uf.make_set(1);
+    /// uf.make_set(2);
+    ///
+    /// assert!(uf.union(&1, &2));
+    /// assert_eq!(uf.union(&1, &2), false);
+    /// assert_eq!(uf.union(&1, &3), false);
+    /// ```
+    pub fn union(&mut self, a: &N, b: &N) -> bool {
+        let (a, b) = match (self.index.get(a), self.index.get(b)) {
+            (Some(a), Some(b)) => (*a, *b),
+            _ => return false,
+        };
+
+        let (a, b) = (self.root(a), self.root(b));
+
+        if a == b {
+            return false;
+        }
+
+        // Attach the shorter tree below the taller one, so that trees stay shallow.
+        let (child, parent) = if self.rank[a] < self.rank[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+
+        self.parent[child] = parent;
+        self.size[parent] += self.size[child];
+        self.num_sets -= 1;
+
+        if self.rank[child] == self.rank[parent] {
+            self.rank[parent] += 1;
+        }
+
+        true
+    }
+
+    /// Returns the number of elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::unionfind::UnionFind;
+    /// let mut uf: UnionFind<i32> = UnionFind::new();
+    ///
+    /// uf.make_set(1);
+    /// uf.make_set(2);
+    /// uf.union(&1, &2);
+    ///
+    /// assert_eq!(uf.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns `true` if there are no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::unionfind::UnionFind;
+    /// let mut uf: UnionFind<i32> = UnionFind::new();
+    /// assert!(uf.is_empty());
+    ///
+    /// uf.make_set(1);
+    /// assert_eq!(uf.is_empty(), false);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the number of disjoint sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::unionfind::UnionFind;
+    /// let mut uf: UnionFind<i32> = UnionFind::new();
+    ///
+    /// uf.make_set(1);
+    /// uf.make_set(2);
+    /// uf.make_set(3);
+    /// assert_eq!(uf.num_sets(), 3);
+    ///
+    /// uf.union(&1, &2);
+    /// assert_eq!(uf.num_sets(), 2);
+    /// ```
+    pub fn num_sets(&self) -> usize {
+        self.num_sets
+    }
+
+    /// Returns the number of elements in the set containing the given element, or `None` if the
+    /// element doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::unionfind::UnionFind;
+    /// let mut uf: UnionFind<i32> = UnionFind::new();
+    ///
+    /// uf.make_set(1);
+    /// uf.make_set(2);
+    /// uf.make_set(3);
+    /// uf.union(&1, &2);
+    ///
+    /// assert_eq!(uf.set_size(&2), Some(2));
+    /// assert_eq!(uf.set_size(&3), Some(1));
+    /// assert_eq!(uf.set_size(&4), None);
+    /// ```
+    pub fn set_size(&mut self, element: &N) -> Option<usize> {
+        let i = *self.index.get(element)?;
+        let root = self.root(i);
+
+        Some(self.size[root])
+    }
+
+    /// Returns every set, each as a list of its elements. Sets are ordered by their earliest added
+    /// element, and the elements within a set are in the order they were added.
+    ///
+    /// Unlike `find`, this takes `&self`, so it doesn't compress any paths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisgraph::unionfind::UnionFind;
+    /// let mut uf: UnionFind<char> = UnionFind::new();
+    ///
+    /// for c in ['a', 'b', 'c', 'd'] {
+    ///     uf.make_set(c);
+    /// }
+    ///
+    /// uf.union(&'d', &'b');
+    ///
+    /// assert_eq!(uf.sets(), vec![vec![&'a'], vec![&'b', &'d'], vec![&'c']]);
+    /// ```
+    pub fn sets(&self) -> Vec<Vec<&N>> {
+        let mut position: HashMap<usize, usize> = HashMap::new();
+        let mut sets: Vec<Vec<&N>> = Vec::new();
+
+        for (i, element) in self.elements.iter().enumerate() {
+            let mut root = i;
+
+            while self.parent[root] != root {
+                root = self.parent[root];
+            }
+
+            let next = sets.len();
+            let pos = *position.entry(root).or_insert(next);
+
+            if pos == next {
+                sets.push(Vec::new());
+            }
+
+            sets[pos].push(element);
+        }
+
+        sets
+    }
+
+    /// Finds the root of the tree containing index `i`, pointing every index on the way directly at it.
+    fn root(&mut self, i: usize) -> usize {
+        let mut root = i;
+
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut curr = i;
+
+        while self.parent[curr] != root {
+            let next = self.parent[curr];
+            self.parent[curr] = root;
+            curr = next;
+        }
+
+        root
+    }
+}
@@ -1,6 +1,76 @@
-use std::collections::BTreeSet;
+#![allow(clippy::bool_assert_comparison)]
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+use ferrisgraph::{
+    graph::{
+        graph::GraphError,
+        paths::{AllPairsShortestPaths, ShortestPaths},
+        tsp::{Tour, EXACT_MATCHING_LIMIT, HELD_KARP_LIMIT},
+        Graph, OrderedFloat,
+    },
+    graph_with_nodes,
+    random::Lcg,
+    unionfind::UnionFind,
+};
+
+// A small linear congruential generator, so that random graphs are the same on every run. Each call returns
+// a number below `m`.
+fn lcg(seed: u64) -> impl FnMut(u64) -> u64 {
+    let mut rng = Lcg::new(seed);
+    move |m| rng.below(m)
+}
+
+// Finds the number of pairs and the weight of the best matching by trying every matching
+fn brute_force_assignment(
+    weights: &[Vec<Option<i64>>],
+    i: usize,
+    used: &mut Vec<bool>,
+) -> (usize, i64) {
+    if i == weights.len() {
+        return (0, 0);
+    }
+
+    let mut best = brute_force_assignment(weights, i + 1, used);
+
+    for j in 0..used.len() {
+        if let (Some(w), false) = (weights[i][j], used[j]) {
+            used[j] = true;
+            let (count, weight) = brute_force_assignment(weights, i + 1, used);
+            used[j] = false;
+
+            if (count + 1, -(weight + w)) > (best.0, -best.1) {
+                best = (count + 1, weight + w);
+            }
+        }
+    }
+
+    best
+}
+
+// Finds the cheapest tour from node 0 by trying every order of the other nodes
+fn brute_force_tour(dist: &[Vec<Option<i64>>], order: &mut Vec<usize>, best: &mut Option<i64>) {
+    let n = dist.len();
 
-use ferrisgraph::{graph::Graph, graph_with_nodes};
+    if order.len() == n {
+        let cost: Option<i64> = (0..n).map(|i| dist[order[i]][order[(i + 1) % n]]).sum();
+
+        if let Some(cost) = cost {
+            *best = Some(best.map_or(cost, |b| b.min(cost)));
+        }
+
+        return;
+    }
+
+    for v in 1..n {
+        if !order.contains(&v) {
+            order.push(v);
+            brute_force_tour(dist, order, best);
+            order.pop();
+        }
+    }
+}
 
 #[test]
 fn test_node_insertion_and_contains() {
@@ -342,11 +412,11 @@ fn test_djikstra() {
 
     g.add_undirected_edge(&4, &5, Some(8));
 
-    let res = g.djikstra(&0, 1, 0);
+    let res = g.djikstra(&0);
 
     assert!(res.is_ok());
 
-    let (dist, pred) = res.unwrap();
+    let ShortestPaths { dist, pred, .. } = res.unwrap();
 
     assert_eq!(*dist.get(&0).unwrap(), 0);
     assert_eq!(*dist.get(&1).unwrap(), 13);
@@ -394,3 +464,2257 @@ fn test_has_cycle() {
 
     assert!(g.has_cycle());
 }
+
+#[test]
+fn test_strongly_connected_components() {
+    let mut g: Graph<i32, i32> = graph_with_nodes!(0, 1, 2, 3, 4, 5, 6, 7);
+
+    g.add_edge(&0, &1, None);
+    g.add_edge(&1, &2, None);
+    g.add_edge(&2, &0, None);
+
+    g.add_edge(&2, &3, Some(5));
+    g.add_edge(&3, &4, None);
+    g.add_edge(&4, &5, None);
+    g.add_edge(&5, &3, None);
+
+    g.add_edge(&6, &5, None);
+    g.add_edge(&6, &7, None);
+    g.add_edge(&7, &6, None);
+    g.add_edge(&7, &7, None);
+
+    let expected = [vec![&0, &1, &2], vec![&3, &4, &5]];
+
+    for (components, component_of) in [g.tarjan_scc(), g.kosaraju_scc()] {
+        assert_eq!(components.len(), 3);
+        assert_eq!(component_of.len(), 8);
+
+        for component in expected.iter() {
+            assert!(components.contains(component));
+        }
+        assert!(components.contains(&vec![&6, &7]));
+
+        // Components are topologically ordered
+        assert!(component_of[&0] < component_of[&3]);
+        assert!(component_of[&6] < component_of[&3]);
+
+        for (i, component) in components.iter().enumerate() {
+            for n in component {
+                assert_eq!(component_of[n], i);
+            }
+        }
+    }
+
+    assert_eq!(g.strongly_connected_components(), g.tarjan_scc());
+}
+
+#[test]
+fn test_scc_large_graph() {
+    let mut g: Graph<i32, i32> = Graph::new();
+
+    for i in 0..100_000 {
+        g.add_node(i);
+    }
+
+    for i in 0..99_999 {
+        g.add_edge(&i, &(i + 1), None);
+    }
+
+    let (components, _) = g.tarjan_scc();
+    assert_eq!(components.len(), 100_000);
+    assert_eq!(components[0], vec![&0]);
+
+    g.add_edge(&99_999, &0, None);
+
+    let (components, _) = g.strongly_connected_components();
+    assert_eq!(components.len(), 1);
+
+    let (components, _) = g.kosaraju_scc();
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0].len(), 100_000);
+}
+
+#[test]
+fn test_condensation() {
+    let mut g: Graph<i32, i32> = graph_with_nodes!(0, 1, 2, 3, 4, 5);
+
+    g.add_edge(&0, &1, Some(1));
+    g.add_edge(&1, &0, Some(1));
+
+    g.add_edge(&0, &2, Some(7));
+    g.add_edge(&1, &2, Some(3));
+    g.add_edge(&1, &2, Some(9));
+
+    g.add_edge(&2, &3, None);
+    g.add_edge(&3, &4, Some(2));
+    g.add_edge(&4, &2, Some(2));
+
+    g.add_edge(&4, &5, Some(6));
+
+    let (condensed, components) = g
+        .condensation(|weights| Some(weights.into_iter().flatten().cloned().collect::<Vec<i32>>()));
+
+    assert_eq!(components, vec![vec![&0, &1], vec![&2, &3, &4], vec![&5]]);
+    assert_eq!(condensed.num_nodes(), 3);
+    assert_eq!(condensed.num_edges(), 2);
+    assert!(condensed.is_edge(&0, &1, &Some(vec![7, 3, 9])));
+    assert!(condensed.is_edge(&1, &2, &Some(vec![6])));
+    assert!(!condensed.has_cycle());
+
+    let (condensed, _) = g.condensation(|weights| weights.into_iter().flatten().min().cloned());
+
+    assert!(condensed.is_edge(&0, &1, &Some(3)));
+    assert!(condensed.is_edge(&1, &2, &Some(6)));
+}
+
+#[test]
+fn test_topological_sort() {
+    let mut g: Graph<i32, i32> = graph_with_nodes!(0, 1, 2, 3, 4, 5);
+
+    g.add_edge(&5, &2, None);
+    g.add_edge(&5, &0, None);
+    g.add_edge(&4, &0, None);
+    g.add_edge(&4, &1, None);
+    g.add_edge(&2, &3, None);
+    g.add_edge(&3, &1, Some(3));
+    g.add_edge(&3, &1, Some(4));
+
+    let order = g.topological_sort().unwrap();
+    assert_eq!(order.len(), 6);
+
+    let position = |n: &i32| order.iter().position(|x| *x == n).unwrap();
+
+    for node in g.nodes() {
+        for dst in g.connections(node).unwrap().unwrap_or_default() {
+            assert!(position(node) < position(dst));
+        }
+    }
+
+    let levels = g.topological_levels().unwrap();
+    assert_eq!(levels, vec![vec![&4, &5], vec![&0, &2], vec![&3], vec![&1]]);
+
+    g.add_edge(&1, &5, None);
+
+    let err = g.topological_sort().unwrap_err();
+    assert_eq!(err.cycle.first(), err.cycle.last());
+    for pair in err.cycle.windows(2) {
+        assert!(g.is_connected(pair[0], pair[1]));
+    }
+
+    let err = g.topological_levels().unwrap_err();
+    assert_eq!(err.cycle.first(), err.cycle.last());
+
+    let mut g: Graph<i32, i32> = graph_with_nodes!(0, 1);
+    g.add_edge(&0, &1, None);
+    g.add_edge(&1, &1, None);
+
+    assert_eq!(g.topological_sort().unwrap_err().cycle, vec![&1, &1]);
+    assert_eq!(g.topological_levels().unwrap_err().cycle, vec![&1, &1]);
+}
+
+#[test]
+fn test_bellman_ford() {
+    let mut g: Graph<i32, i32> = graph_with_nodes!(0, 1, 2, 3, 4, 5);
+
+    g.add_edge(&0, &1, Some(6));
+    g.add_edge(&0, &2, Some(7));
+    g.add_edge(&1, &2, Some(8));
+    g.add_edge(&1, &3, Some(5));
+    g.add_edge(&1, &4, Some(-4));
+    g.add_edge(&2, &3, Some(-3));
+    g.add_edge(&2, &4, Some(9));
+    g.add_edge(&3, &1, Some(-2));
+    g.add_edge(&4, &3, Some(7));
+    g.add_edge(&4, &5, None);
+
+    let ShortestPaths { dist, pred, .. } = g.bellman_ford(&0).unwrap();
+
+    assert_eq!(dist.len(), 6);
+    assert_eq!(*dist.get(&0).unwrap(), 0);
+    assert_eq!(*dist.get(&1).unwrap(), 2);
+    assert_eq!(*dist.get(&2).unwrap(), 7);
+    assert_eq!(*dist.get(&3).unwrap(), 4);
+    assert_eq!(*dist.get(&4).unwrap(), -2);
+    assert_eq!(*dist.get(&5).unwrap(), -1);
+
+    assert_eq!(*pred.get(&0).unwrap(), None);
+    assert_eq!(*pred.get(&1).unwrap(), Some(&3));
+    assert_eq!(*pred.get(&2).unwrap(), Some(&0));
+    assert_eq!(*pred.get(&3).unwrap(), Some(&2));
+    assert_eq!(*pred.get(&4).unwrap(), Some(&1));
+    assert_eq!(*pred.get(&5).unwrap(), Some(&4));
+
+    // Agrees with djikstra when weights are non-negative
+    let mut g: Graph<i32, i32> = graph_with_nodes!(0, 1, 2, 3);
+    g.add_undirected_edge(&0, &1, Some(4));
+    g.add_undirected_edge(&0, &2, Some(1));
+    g.add_undirected_edge(&2, &1, Some(2));
+    g.add_undirected_edge(&1, &3, None);
+
+    let bf = g.bellman_ford(&0).unwrap();
+    let dj = g.djikstra(&0).unwrap();
+    assert_eq!(bf.dist, dj.dist);
+
+    assert!(g.bellman_ford(&9).is_err());
+}
+
+#[test]
+fn test_bellman_ford_negative_cycle() {
+    let mut g: Graph<i32, i32> = graph_with_nodes!(0, 1, 2, 3, 4);
+
+    g.add_edge(&0, &1, Some(1));
+    g.add_edge(&1, &2, Some(1));
+    g.add_edge(&2, &3, Some(-4));
+    g.add_edge(&3, &1, Some(2));
+    g.add_edge(&3, &4, Some(1));
+
+    let cycle = match g.bellman_ford(&0) {
+        Err(GraphError::NegativeCycle(cycle)) => cycle,
+        res => panic!("Expected a negative cycle, got {:?}", res),
+    };
+
+    assert_eq!(cycle.len(), 4);
+    assert_eq!(cycle.first(), cycle.last());
+    for n in [&1, &2, &3] {
+        assert!(cycle.contains(&n));
+    }
+    for pair in cycle.windows(2) {
+        assert!(g.is_connected(pair[0], pair[1]));
+    }
+
+    // The cycle is not reachable from 4
+    assert!(g.bellman_ford(&4).is_ok());
+
+    // Distances on a negative cycle go below the smallest weight instead of saturating there
+    let mut g: Graph<i32, i8> = graph_with_nodes!(0, 1, 2);
+
+    g.add_edge(&0, &1, Some(-100));
+    g.add_edge(&1, &0, Some(50));
+
+    assert_eq!(
+        g.bellman_ford(&0).unwrap_err(),
+        GraphError::NegativeCycle(vec![&1, &0, &1])
+    );
+    assert!(g.johnson().is_err());
+
+    // Without a cycle, a path that is too short for the weight type is an error
+    let mut g: Graph<i32, i8> = graph_with_nodes!(0, 1, 2);
+
+    g.add_edge(&0, &1, Some(-100));
+    g.add_edge(&1, &2, Some(-100));
+
+    assert_eq!(g.bellman_ford(&0).unwrap_err(), GraphError::WeightOverflow);
+}
+
+#[test]
+fn test_all_pairs_shortest_paths() {
+    let mut g: Graph<i32, i32> = graph_with_nodes!(0, 1, 2, 3, 4, 5);
+
+    g.add_edge(&0, &1, Some(3));
+    g.add_edge(&0, &2, Some(8));
+    g.add_edge(&0, &4, Some(-4));
+    g.add_edge(&1, &3, Some(1));
+    g.add_edge(&1, &4, Some(7));
+    g.add_edge(&2, &1, Some(4));
+    g.add_edge(&3, &0, Some(2));
+    g.add_edge(&3, &2, Some(-5));
+    g.add_edge(&4, &3, Some(6));
+    g.add_edge(&5, &0, None);
+
+    let expected = [
+        [0, 1, -3, 2, -4],
+        [3, 0, -4, 1, -1],
+        [7, 4, 0, 5, 3],
+        [2, -1, -5, 0, -2],
+        [8, 5, 1, 6, 0],
+    ];
+
+    for AllPairsShortestPaths { dist, next, .. } in
+        [g.floyd_warshall().unwrap(), g.johnson().unwrap()]
+    {
+        assert_eq!(dist.len(), 6);
+
+        for (u, row) in expected.iter().enumerate() {
+            let u = u as i32;
+
+            assert_eq!(dist[&u].len(), 5);
+            assert!(!dist[&u].contains_key(&5));
+
+            for (v, d) in row.iter().enumerate() {
+                let v = v as i32;
+                assert_eq!(dist[&u][&v], *d);
+
+                // Following next hops gives a path with the expected length
+                let mut curr = u;
+                let mut len = 0;
+
+                while curr != v {
+                    let hop = *next[&curr][&v];
+                    len += g
+                        .edges(&curr)
+                        .unwrap()
+                        .unwrap()
+                        .into_iter()
+                        .filter(|(dst, _)| **dst == hop)
+                        .map(|(_, w)| w.unwrap_or(1))
+                        .min()
+                        .unwrap();
+                    curr = hop;
+                }
+
+                assert_eq!(len, *d);
+            }
+        }
+
+        assert_eq!(dist[&5].len(), 6);
+        assert_eq!(*next[&5][&5], 5);
+
+        for v in 0..5 {
+            assert_eq!(dist[&5][&v], 1 + expected[0][v as usize]);
+            assert_eq!(*next[&5][&v], 0);
+        }
+    }
+
+    g.add_edge(&5, &5, Some(-1));
+
+    assert!(g.floyd_warshall().is_err());
+    assert!(g.johnson().is_err());
+
+    // Reweighting works near the limits of the weight type, and fails when a weight no longer fits
+    let mut g: Graph<i32, i8> = graph_with_nodes!(0, 1, 2);
+
+    g.add_edge(&0, &1, Some(-128));
+    g.add_edge(&1, &2, Some(127));
+
+    let paths = g.johnson().unwrap();
+    assert_eq!(paths.dist[&0][&1], -128);
+    assert_eq!(paths.dist[&0][&2], -1);
+    assert_eq!(paths.dist[&1][&2], 127);
+
+    g.add_edge(&2, &1, Some(127));
+    assert_eq!(g.johnson().unwrap_err(), GraphError::WeightOverflow);
+}
+
+#[test]
+fn test_astar() {
+    let mut g: Graph<i32, i32> = graph_with_nodes!(0, 1, 2, 3, 4, 5, 6);
+
+    g.add_undirected_edge(&0, &1, Some(14));
+    g.add_undirected_edge(&0, &2, Some(9));
+    g.add_undirected_edge(&0, &3, Some(7));
+    g.add_undirected_edge(&1, &4, Some(5));
+    g.add_undirected_edge(&2, &1, Some(4));
+    g.add_undirected_edge(&2, &5, Some(3));
+    g.add_undirected_edge(&2, &3, Some(10));
+    g.add_undirected_edge(&3, &5, Some(15));
+    g.add_undirected_edge(&4, &5, Some(8));
+
+    // A zero heuristic behaves like djikstra
+    let path = g.astar(&0, &4, |_| 0).unwrap().unwrap();
+    assert_eq!(path.nodes, vec![&0, &2, &1, &4]);
+    assert_eq!(path.cost, 18);
+
+    let dist = g.djikstra(&0).unwrap().dist;
+    for n in 0..6 {
+        let path = g.astar(&0, &n, |_| 0).unwrap().unwrap();
+        assert_eq!(path.cost, dist[&n]);
+    }
+
+    // The exact remaining distance is an admissible heuristic
+    let to_four = g.djikstra(&4).unwrap().dist;
+    let path = g.astar(&0, &4, |n| to_four[n]).unwrap().unwrap();
+    assert_eq!(path.nodes, vec![&0, &2, &1, &4]);
+    assert_eq!(path.cost, 18);
+
+    let path = g.astar(&3, &3, |_| 0).unwrap().unwrap();
+    assert_eq!(path.nodes, vec![&3]);
+    assert!(path.edges.is_empty());
+    assert_eq!(path.cost, 0);
+
+    assert_eq!(g.astar(&0, &6, |_| 0), Ok(None));
+    assert!(g.astar(&0, &7, |_| 0).is_err());
+    assert!(g.astar(&7, &0, |_| 0).is_err());
+}
+
+#[test]
+fn test_paths() {
+    let mut g: Graph<i32, i32> = graph_with_nodes!(0, 1, 2, 3, 4);
+
+    g.add_edge(&0, &1, Some(4));
+    g.add_edge(&0, &1, Some(2));
+    g.add_edge(&1, &2, None);
+    g.add_edge(&0, &2, Some(9));
+    g.add_edge(&2, &3, Some(1));
+
+    let tree = g.bfs(&0).unwrap();
+    let path = tree.path_to(&3).unwrap();
+    assert_eq!(path.nodes, vec![&0, &2, &3]);
+    assert_eq!(path.edges, vec![(&0, &2, &Some(9)), (&2, &3, &Some(1))]);
+    assert_eq!(path.cost, 2);
+    assert!(tree.path_to(&4).is_none());
+
+    let path = tree.path_to(&0).unwrap();
+    assert_eq!(path.nodes, vec![&0]);
+    assert_eq!(path.cost, 0);
+
+    let expected_nodes = vec![&0, &1, &2, &3];
+    let expected_edges = vec![(&0, &1, &Some(2)), (&1, &2, &None), (&2, &3, &Some(1))];
+
+    let paths = g.djikstra(&0).unwrap();
+    let path = paths.path_to(&3).unwrap();
+    assert_eq!(path.nodes, expected_nodes);
+    assert_eq!(path.edges, expected_edges);
+    assert_eq!(path.cost, 4);
+    assert!(paths.path_to(&4).is_none());
+
+    let path = g.bellman_ford(&0).unwrap().path_to(&3).unwrap();
+    assert_eq!(path.edges, expected_edges);
+    assert_eq!(path.cost, 4);
+
+    let path = g.astar(&0, &3, |_| 0).unwrap().unwrap();
+    assert_eq!(path.edges, expected_edges);
+    assert_eq!(path.cost, 4);
+
+    for paths in [g.floyd_warshall().unwrap(), g.johnson().unwrap()] {
+        let path = paths.path(&0, &3).unwrap();
+        assert_eq!(path.nodes, expected_nodes);
+        assert_eq!(path.edges, expected_edges);
+        assert_eq!(path.cost, 4);
+
+        let path = paths.path(&1, &1).unwrap();
+        assert_eq!(path.nodes, vec![&1]);
+        assert_eq!(path.cost, 0);
+
+        assert!(paths.path(&3, &0).is_none());
+        assert!(paths.path(&7, &0).is_none());
+    }
+}
+
+#[test]
+fn test_float_weights() {
+    let mut g: Graph<&str, OrderedFloat<f64>> = graph_with_nodes!("USD", "EUR", "GBP", "JPY");
+
+    // Exchange rates, stored as -ln(rate) so that a negative cycle is an arbitrage opportunity
+    let rate = |r: f64| Some(OrderedFloat(-r.ln()));
+
+    g.add_edge(&"USD", &"EUR", rate(0.9));
+    g.add_edge(&"EUR", &"USD", rate(1.1));
+    g.add_edge(&"EUR", &"GBP", rate(0.85));
+    g.add_edge(&"GBP", &"USD", rate(1.4));
+    g.add_edge(&"USD", &"JPY", rate(150.0));
+
+    let paths = g.djikstra(&"JPY").unwrap();
+    assert_eq!(paths.dist.len(), 1);
+
+    // 0.9 * 0.85 * 1.4 > 1
+    match g.bellman_ford(&"USD") {
+        Err(GraphError::NegativeCycle(cycle)) => {
+            assert_eq!(cycle.len(), 4);
+            assert!(cycle.contains(&&"GBP"));
+        }
+        res => panic!("Expected a negative cycle, got {:?}", res),
+    }
+
+    assert!(g.remove_edge(&"GBP", &"USD", rate(1.4)));
+    g.add_edge(&"GBP", &"USD", rate(1.2));
+
+    let paths = g.bellman_ford(&"USD").unwrap();
+    let path = paths.path_to(&"GBP").unwrap();
+    assert_eq!(path.nodes, vec![&"USD", &"EUR", &"GBP"]);
+    assert!((path.cost.0 + (0.9f64 * 0.85).ln()).abs() < 1e-12);
+
+    let mut g: Graph<i32, OrderedFloat<f32>> = graph_with_nodes!(0, 1, 2);
+    g.add_edge(&0, &1, Some(OrderedFloat(0.5)));
+    g.add_edge(&1, &2, Some(OrderedFloat(0.25)));
+    g.add_edge(&0, &2, Some(OrderedFloat(1.0)));
+    g.add_edge(&0, &2, Some(OrderedFloat(f32::NAN)));
+
+    assert!(g.is_edge(&0, &2, &Some(OrderedFloat(f32::NAN))));
+    assert!(!g.add_edge(&0, &2, Some(OrderedFloat(-f32::NAN))));
+
+    let paths = g.djikstra(&0).unwrap();
+    assert_eq!(paths.dist[&2], OrderedFloat(0.75));
+}
+
+#[test]
+fn test_measure_weights() {
+    let mut g: Graph<&str, Duration> = graph_with_nodes!("Home", "Station", "Office");
+
+    g.add_edge(&"Home", &"Station", Some(Duration::from_secs(600)));
+    g.add_edge(&"Station", &"Office", Some(Duration::from_secs(1500)));
+    g.add_edge(&"Home", &"Office", None);
+
+    let paths = g.djikstra(&"Home").unwrap();
+    assert_eq!(paths.dist[&"Home"], Duration::ZERO);
+    assert_eq!(paths.dist[&"Station"], Duration::from_secs(600));
+    assert_eq!(paths.dist[&"Office"], Duration::from_secs(1));
+
+    // Large weights saturate instead of overflowing
+    let mut g: Graph<i32, u8> = graph_with_nodes!(0, 1, 2);
+
+    g.add_edge(&0, &1, Some(200));
+    g.add_edge(&1, &2, Some(200));
+
+    assert_eq!(g.djikstra(&0).unwrap().dist[&2], u8::MAX);
+    assert_eq!(g.bellman_ford(&0).unwrap().dist[&2], u8::MAX);
+    assert_eq!(g.floyd_warshall().unwrap().dist[&0][&2], u8::MAX);
+}
+
+#[test]
+fn test_minimum_spanning_forest() {
+    let mut g: Graph<char, i32> =
+        graph_with_nodes!('a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'x', 'y');
+
+    g.add_undirected_edge(&'a', &'b', Some(4));
+    g.add_undirected_edge(&'a', &'h', Some(8));
+    g.add_undirected_edge(&'b', &'c', Some(8));
+    g.add_undirected_edge(&'b', &'h', Some(11));
+    g.add_undirected_edge(&'c', &'d', Some(7));
+    g.add_undirected_edge(&'c', &'f', Some(4));
+    g.add_undirected_edge(&'c', &'i', Some(2));
+    g.add_undirected_edge(&'d', &'e', Some(9));
+    g.add_undirected_edge(&'d', &'f', Some(14));
+    g.add_undirected_edge(&'e', &'f', Some(10));
+    g.add_undirected_edge(&'f', &'g', Some(2));
+    g.add_undirected_edge(&'g', &'h', Some(1));
+    g.add_undirected_edge(&'g', &'i', Some(6));
+    g.add_undirected_edge(&'h', &'i', Some(7));
+
+    // A second component, with a one-way edge and a parallel edge
+    g.add_edge(&'y', &'x', Some(3));
+    g.add_undirected_edge(&'x', &'y', None);
+    g.add_edge(&'x', &'x', Some(-5));
+
+    for forest in [g.minimum_spanning_forest(), g.kruskal(), g.prim()] {
+        assert_eq!(forest.weight, 38);
+        assert_eq!(forest.edges.len(), 9);
+
+        assert!(forest.edges.contains(&(&'g', &'h', &Some(1))));
+        assert!(forest.edges.contains(&(&'c', &'i', &Some(2))));
+        assert!(forest.edges.contains(&(&'d', &'e', &Some(9))));
+        assert!(forest.edges.contains(&(&'x', &'y', &None)));
+
+        for (u, v, w) in forest.edges.iter() {
+            assert!(u < v);
+            assert!(g.is_edge(u, v, w) || g.is_edge(v, u, w));
+        }
+    }
+
+    let g: Graph<i32, i32> = graph_with_nodes!(1, 2);
+    assert_eq!(g.kruskal().edges, vec![]);
+    assert_eq!(g.prim().weight, 0);
+}
+
+#[test]
+fn test_union_find() {
+    let mut uf: UnionFind<i32> = UnionFind::new();
+
+    for i in 0..10 {
+        assert!(uf.make_set(i));
+    }
+
+    assert_eq!(uf.make_set(3), false);
+
+    for i in 0..9 {
+        if i % 2 == 0 {
+            assert!(uf.union(&i, &(i + 2)) || i == 8);
+        }
+    }
+
+    assert!(uf.connected(&0, &8));
+    assert_eq!(uf.connected(&0, &1), false);
+    assert_eq!(uf.find(&10), None);
+
+    assert!(uf.union(&1, &0));
+    assert!(uf.connected(&1, &6));
+    let root = *uf.find(&6).unwrap();
+    assert_eq!(uf.find(&1), Some(&root));
+    assert_eq!(uf.union(&9, &10), false);
+}
+
+#[test]
+fn test_union_find_sets() {
+    let mut uf: UnionFind<i32> = UnionFind::new();
+    assert!(uf.is_empty());
+    assert_eq!(uf.sets(), Vec::<Vec<&i32>>::new());
+
+    for i in 0..10 {
+        uf.make_set(i);
+    }
+
+    assert_eq!(uf.len(), 10);
+    assert_eq!(uf.num_sets(), 10);
+
+    // Group by remainder mod 3
+    for i in 3..10 {
+        assert!(uf.union(&i, &(i - 3)));
+    }
+
+    assert_eq!(uf.union(&9, &0), false);
+    assert_eq!(uf.len(), 10);
+    assert_eq!(uf.num_sets(), 3);
+    assert_eq!(uf.set_size(&9), Some(4));
+    assert_eq!(uf.set_size(&5), Some(3));
+    assert_eq!(uf.set_size(&10), None);
+
+    assert_eq!(
+        uf.sets(),
+        vec![vec![&0, &3, &6, &9], vec![&1, &4, &7], vec![&2, &5, &8]]
+    );
+
+    uf.union(&1, &2);
+    assert_eq!(uf.num_sets(), 2);
+    assert_eq!(uf.set_size(&8), Some(6));
+    assert_eq!(uf.sets()[1], vec![&1, &2, &4, &5, &7, &8]);
+}
+
+#[test]
+fn test_weakly_connected_components() {
+    let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4, 5, 6, 7);
+
+    g.add_edge(&1, &2, None);
+    g.add_edge(&3, &2, None);
+    g.add_edge(&5, &4, Some(2));
+    g.add_edge(&4, &5, Some(3));
+    g.add_edge(&6, &6, None);
+
+    let (components, component_of) = g.weakly_connected_components();
+
+    assert_eq!(
+        components,
+        vec![vec![&1, &2, &3], vec![&4, &5], vec![&6], vec![&7]]
+    );
+    assert_eq!(component_of[&3], 0);
+    assert_eq!(component_of[&5], 1);
+    assert_eq!(component_of[&7], 3);
+
+    // Strongly connected components are never coarser than weakly connected ones
+    let (strong, _) = g.strongly_connected_components();
+    assert_eq!(strong.len(), 6);
+
+    let g: Graph<i32, i32> = Graph::new();
+    assert_eq!(g.weakly_connected_components().0.len(), 0);
+
+    let mut g: Graph<i32, i32> = Graph::new();
+
+    for i in 0..100_000 {
+        g.add_node(i);
+    }
+
+    for i in 0..99_999 {
+        if i % 1000 != 999 {
+            g.add_edge(&(i + 1), &i, None);
+        }
+    }
+
+    let (components, _) = g.weakly_connected_components();
+    assert_eq!(components.len(), 100);
+    assert!(components.iter().all(|c| c.len() == 1000));
+}
+
+#[test]
+fn test_max_flow() {
+    // CLRS figure 26.1, with the 16 capacity edge split in two parallel edges
+    let mut g: Graph<&str, i32> = graph_with_nodes!("s", "v1", "v2", "v3", "v4", "t");
+
+    g.add_edge(&"s", &"v1", Some(10));
+    g.add_edge(&"s", &"v1", Some(6));
+    g.add_edge(&"s", &"v2", Some(13));
+    g.add_edge(&"v1", &"v3", Some(12));
+    g.add_edge(&"v2", &"v1", Some(4));
+    g.add_edge(&"v2", &"v4", Some(14));
+    g.add_edge(&"v3", &"v2", Some(9));
+    g.add_edge(&"v3", &"t", Some(20));
+    g.add_edge(&"v4", &"v3", Some(7));
+    g.add_edge(&"v4", &"t", Some(4));
+    g.add_edge(&"t", &"t", Some(100));
+
+    for flow in [
+        g.max_flow(&"s", &"t").unwrap(),
+        g.dinic(&"s", &"t").unwrap(),
+        g.edmonds_karp(&"s", &"t").unwrap(),
+    ] {
+        assert_eq!(flow.value, 23);
+        assert!(flow.flow[&(&"s", &"v1")] <= 16);
+        assert_eq!(flow.flow[&(&"v3", &"t")] + flow.flow[&(&"v4", &"t")], 23);
+        assert_eq!(
+            flow.source_side,
+            BTreeSet::from([&"s", &"v1", &"v2", &"v4"])
+        );
+        assert_eq!(
+            flow.cut,
+            vec![(&"v1", &"v3", 12), (&"v4", &"t", 4), (&"v4", &"v3", 7)]
+        );
+    }
+
+    assert_eq!(g.max_flow(&"t", &"s").unwrap().value, 0);
+    assert_eq!(g.max_flow(&"s", &"s").unwrap().value, 0);
+    assert_eq!(
+        g.max_flow(&"s", &"x").unwrap_err(),
+        GraphError::NodeNotFound(&"x")
+    );
+}
+
+#[test]
+fn test_max_flow_random() {
+    let mut next = lcg(42);
+
+    for _ in 0..20 {
+        let mut g: Graph<u64, u64> = Graph::new();
+
+        for i in 0..30 {
+            g.add_node(i);
+        }
+
+        for _ in 0..120 {
+            let (u, v) = (next(30), next(30));
+            let c = next(4);
+            g.add_edge(&u, &v, if c == 0 { None } else { Some(next(20)) });
+        }
+
+        let dinic = g.dinic(&0, &29).unwrap();
+        let edmonds_karp = g.edmonds_karp(&0, &29).unwrap();
+
+        assert_eq!(dinic.value, edmonds_karp.value);
+
+        for flow in [dinic, edmonds_karp] {
+            let cut: u64 = flow.cut.iter().map(|(_, _, c)| c).sum();
+            assert_eq!(cut, flow.value);
+
+            // Every node other than the source and sink has as much flow entering as leaving
+            for n in 1..29 {
+                let into: u64 = flow
+                    .flow
+                    .iter()
+                    .filter(|((_, v), _)| **v == n)
+                    .map(|(_, f)| f)
+                    .sum();
+                let out: u64 = flow
+                    .flow
+                    .iter()
+                    .filter(|((u, _), _)| **u == n)
+                    .map(|(_, f)| f)
+                    .sum();
+
+                assert_eq!(into, out);
+            }
+
+            for ((u, v), f) in flow.flow.iter() {
+                let capacity: u64 = g
+                    .edges(u)
+                    .unwrap()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|(dst, _)| dst == v)
+                    .map(|(_, e)| e.unwrap_or(1))
+                    .sum();
+
+                assert!(*f <= capacity);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_min_cost_flow() {
+    // Two warehouses supplying three shops, weighted by (capacity, cost)
+    let mut g: Graph<&str, (i64, i64)> = graph_with_nodes!("W1", "W2", "S1", "S2", "S3");
+
+    g.add_edge(&"W1", &"S1", Some((100, 4)));
+    g.add_edge(&"W1", &"S2", Some((100, 6)));
+    g.add_edge(&"W1", &"S3", Some((100, 9)));
+    g.add_edge(&"W2", &"S1", Some((100, 5)));
+    g.add_edge(&"W2", &"S2", Some((100, 3)));
+    g.add_edge(&"W2", &"S3", Some((100, 8)));
+    g.add_edge(&"S1", &"S3", Some((5, 2)));
+    g.add_edge(&"S1", &"S3", Some((5, 3)));
+
+    let supplies = HashMap::from([
+        (&"W1", 30),
+        (&"W2", 25),
+        (&"S1", -20),
+        (&"S2", -15),
+        (&"S3", -20),
+    ]);
+    let capacity = |e: &Option<(i64, i64)>| e.map_or(0, |e| e.0);
+    let cost = |e: &Option<(i64, i64)>| e.map_or(0, |e| e.1);
+
+    let res = g.min_cost_flow(&supplies, capacity, cost).unwrap();
+
+    // W1 -> S1 (30, of which 5 continue to S3 at cost 2 and 5 at cost 3), W2 -> S2 (15), W2 -> S3 (10)
+    assert_eq!(res.cost, 30 * 4 + 5 * 2 + 5 * 3 + 15 * 3 + 10 * 8);
+    assert_eq!(res.flow[&(&"W1", &"S1", &Some((100, 4)))], 30);
+    assert_eq!(res.flow[&(&"S1", &"S3", &Some((5, 2)))], 5);
+    assert_eq!(res.flow[&(&"S1", &"S3", &Some((5, 3)))], 5);
+    assert_eq!(res.flow[&(&"W2", &"S3", &Some((100, 8)))], 10);
+    assert_eq!(res.flow[&(&"W1", &"S3", &Some((100, 9)))], 0);
+    assert_eq!(res.flow.len(), 8);
+
+    // Unbalanced supplies and demands
+    let mut unbalanced = supplies.clone();
+    unbalanced.insert(&"S3", -25);
+    assert_eq!(
+        g.min_cost_flow(&unbalanced, capacity, cost).unwrap_err(),
+        GraphError::InfeasibleFlow(5)
+    );
+
+    // Not enough capacity to reach S3 from W1 alone
+    let supplies = HashMap::from([(&"W1", 120), (&"S3", -120)]);
+    assert_eq!(
+        g.min_cost_flow(&supplies, capacity, cost).unwrap_err(),
+        GraphError::InfeasibleFlow(10)
+    );
+
+    let supplies = HashMap::from([(&"W3", 10)]);
+    assert_eq!(
+        g.min_cost_flow(&supplies, capacity, cost).unwrap_err(),
+        GraphError::NodeNotFound(&"W3")
+    );
+
+    // With no supplies, the only flow worth sending goes around cycles with negative cost
+    let mut g: Graph<i32, (i64, i64)> = graph_with_nodes!(1, 2, 3);
+    g.add_edge(&1, &2, Some((4, -3)));
+    g.add_edge(&2, &3, Some((3, 1)));
+    g.add_edge(&3, &1, Some((5, 1)));
+    g.add_edge(&2, &2, Some((2, -1)));
+    g.add_edge(&3, &3, Some((2, 1)));
+
+    let res = g.min_cost_flow(&HashMap::new(), capacity, cost).unwrap();
+
+    assert_eq!(res.cost, -3 - 2);
+    assert_eq!(res.flow[&(&1, &2, &Some((4, -3)))], 3);
+    assert_eq!(res.flow[&(&2, &2, &Some((2, -1)))], 2);
+    assert_eq!(res.flow[&(&3, &3, &Some((2, 1)))], 0);
+}
+
+#[test]
+fn test_bipartition() {
+    let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4, 5, 6, 7);
+
+    // An even cycle, going both ways around, and a separate path
+    g.add_edge(&1, &2, None);
+    g.add_edge(&3, &2, None);
+    g.add_edge(&3, &4, Some(5));
+    g.add_edge(&1, &4, None);
+    g.add_edge(&4, &1, None);
+    g.add_edge(&6, &5, None);
+    g.add_edge(&7, &6, None);
+
+    let (left, right) = g.bipartition().unwrap();
+
+    assert_eq!(left, BTreeSet::from([&1, &3, &5, &7]));
+    assert_eq!(right, BTreeSet::from([&2, &4, &6]));
+
+    // Closing an odd cycle 5 -> 6 -> 7 -> 5
+    g.add_edge(&5, &7, None);
+
+    let err = g.bipartition().unwrap_err();
+    assert_eq!(err.cycle, vec![&6, &5, &7, &6]);
+
+    assert!(g.remove_edge(&5, &7, None));
+    g.add_edge(&1, &3, None);
+
+    let cycle = g.bipartition().unwrap_err().cycle;
+    assert_eq!(cycle.len() % 2, 0);
+    assert_eq!(cycle.first(), cycle.last());
+
+    for pair in cycle.windows(2) {
+        assert!(g.is_connected(pair[0], pair[1]) || g.is_connected(pair[1], pair[0]));
+    }
+
+    let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2);
+    g.add_edge(&2, &2, None);
+    assert_eq!(g.bipartition().unwrap_err().cycle, vec![&2, &2]);
+
+    let g: Graph<i32, i32> = Graph::new();
+    assert_eq!(g.bipartition().unwrap(), (BTreeSet::new(), BTreeSet::new()));
+}
+
+#[test]
+fn test_maximum_bipartite_matching() {
+    let mut g: Graph<&str, i32> =
+        graph_with_nodes!("A", "B", "C", "D", "E", "1", "2", "3", "4", "5");
+
+    g.add_edge(&"A", &"1", None);
+    g.add_edge(&"A", &"2", None);
+    g.add_edge(&"B", &"1", None);
+    g.add_edge(&"C", &"2", None);
+    g.add_edge(&"C", &"3", None);
+    g.add_edge(&"D", &"3", None);
+    g.add_edge(&"D", &"4", None);
+    g.add_edge(&"E", &"3", None);
+    g.add_edge(&"5", &"E", None);
+
+    let matching = g.maximum_bipartite_matching().unwrap();
+
+    assert_eq!(
+        matching,
+        vec![
+            (&"5", &"E"),
+            (&"A", &"2"),
+            (&"B", &"1"),
+            (&"C", &"3"),
+            (&"D", &"4")
+        ]
+    );
+
+    g.add_edge(&"A", &"B", None);
+    assert!(g.maximum_bipartite_matching().is_err());
+
+    // Compare against a maximum flow from a super source to a super sink on random graphs
+    let mut next = lcg(7);
+
+    for _ in 0..20 {
+        let mut g: Graph<u64, u64> = Graph::new();
+        let mut flow: Graph<u64, u64> = Graph::new();
+
+        for i in 0..62 {
+            g.add_node(i);
+            flow.add_node(i);
+        }
+
+        for u in 0..30 {
+            flow.add_edge(&60, &u, None);
+            flow.add_edge(&(u + 30), &61, None);
+        }
+
+        for _ in 0..50 {
+            let (u, v) = (next(30), next(30) + 30);
+            g.add_edge(&u, &v, None);
+            flow.add_edge(&u, &v, None);
+        }
+
+        let matching = g.maximum_bipartite_matching().unwrap();
+        let nodes: BTreeSet<&u64> = matching.iter().flat_map(|(u, v)| [*u, *v]).collect();
+
+        assert_eq!(nodes.len(), 2 * matching.len());
+        assert!(matching.iter().all(|(u, v)| g.is_connected(u, v)));
+        assert_eq!(
+            matching.len() as u64,
+            flow.max_flow(&60, &61).unwrap().value
+        );
+    }
+}
+
+#[test]
+fn test_min_weight_assignment() {
+    let mut g: Graph<&str, i32> = graph_with_nodes!("A", "B", "C", "X", "Y", "Z");
+
+    g.add_edge(&"A", &"X", Some(7));
+    g.add_edge(&"A", &"X", Some(2));
+    g.add_edge(&"Y", &"A", Some(3));
+    g.add_edge(&"B", &"X", Some(1));
+    g.add_edge(&"B", &"Y", Some(-4));
+    g.add_edge(&"C", &"X", Some(6));
+    g.add_edge(&"C", &"Z", None);
+    g.add_edge(&"A", &"B", Some(-100));
+
+    // C -> Z is forbidden, so only two pairs can be matched
+    let res = g
+        .min_weight_assignment(&[&"A", &"B", &"C"], &[&"X", &"Y", &"Z"])
+        .unwrap();
+
+    assert_eq!(res.weight, -2);
+    assert_eq!(
+        res.pairs,
+        vec![(&"A", &"X", &Some(2)), (&"B", &"Y", &Some(-4))]
+    );
+
+    // Allowing C -> Z makes a perfect matching possible, even though it costs more
+    g.add_edge(&"Z", &"C", Some(50));
+
+    let res = g
+        .min_weight_assignment(&[&"C", &"B", &"A"], &[&"X", &"Y", &"Z"])
+        .unwrap();
+
+    assert_eq!(res.weight, 48);
+    assert_eq!(
+        res.pairs,
+        vec![
+            (&"C", &"Z", &Some(50)),
+            (&"B", &"Y", &Some(-4)),
+            (&"A", &"X", &Some(2))
+        ]
+    );
+
+    assert_eq!(
+        g.min_weight_assignment(&[&"A"], &[&"W"]).unwrap_err(),
+        GraphError::NodeNotFound(&"W")
+    );
+    assert_eq!(
+        g.min_weight_assignment(&[&"A", &"B", &"A"], &[&"X"])
+            .unwrap_err(),
+        GraphError::DuplicateNode(&"A")
+    );
+    assert_eq!(
+        g.min_weight_assignment(&[&"A", &"B"], &[&"X", &"B"])
+            .unwrap_err(),
+        GraphError::DuplicateNode(&"B")
+    );
+
+    let res = g.min_weight_assignment(&[], &[&"X"]).unwrap();
+    assert_eq!((res.pairs.len(), res.weight), (0, 0));
+}
+
+#[test]
+fn test_min_weight_assignment_random() {
+    let mut next = lcg(99);
+
+    for _ in 0..50 {
+        let (n, m) = (next(6) as usize + 1, next(6) as usize + 1);
+        let mut g: Graph<usize, i64> = Graph::new();
+        let mut weights = vec![vec![None; m]; n];
+
+        for i in 0..n + m {
+            g.add_node(i);
+        }
+
+        for (i, row) in weights.iter_mut().enumerate() {
+            for (j, weight) in row.iter_mut().enumerate() {
+                match next(4) {
+                    0 => {}
+                    1 => {
+                        g.add_edge(&i, &(n + j), None);
+                    }
+                    _ => {
+                        let w = next(40) as i64 - 10;
+                        g.add_edge(&(n + j), &i, Some(w));
+                        *weight = Some(w);
+                    }
+                }
+            }
+        }
+
+        let left: Vec<usize> = (0..n).collect();
+        let right: Vec<usize> = (n..n + m).collect();
+        let left: Vec<&usize> = left.iter().collect();
+        let right: Vec<&usize> = right.iter().collect();
+
+        let res = g.min_weight_assignment(&left, &right).unwrap();
+
+        assert_eq!(
+            (res.pairs.len(), res.weight),
+            brute_force_assignment(&weights, 0, &mut vec![false; m])
+        );
+    }
+}
+
+#[test]
+fn test_articulation_points_and_bridges() {
+    let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4, 5, 6, 7, 8, 9);
+
+    // Two triangles joined at 3, a bridge 3 - 6, and a pair of parallel edges 6 - 7
+    g.add_undirected_edge(&1, &2, None);
+    g.add_undirected_edge(&2, &3, None);
+    g.add_undirected_edge(&3, &1, None);
+    g.add_undirected_edge(&3, &4, None);
+    g.add_undirected_edge(&4, &5, None);
+    g.add_edge(&5, &3, None);
+    g.add_undirected_edge(&3, &6, Some(2));
+    g.add_undirected_edge(&6, &7, Some(1));
+    g.add_undirected_edge(&6, &7, Some(3));
+    g.add_edge(&7, &8, None);
+    g.add_edge(&8, &8, None);
+
+    assert_eq!(g.articulation_points(), BTreeSet::from([&3, &6, &7]));
+    assert_eq!(g.bridges(), vec![(&3, &6, &Some(2)), (&7, &8, &None)]);
+    assert_eq!(
+        g.biconnected_components(),
+        vec![
+            vec![&1, &2, &3],
+            vec![&3, &4, &5],
+            vec![&3, &6],
+            vec![&6, &7],
+            vec![&7, &8]
+        ]
+    );
+
+    // Compare against removing each node and edge on random graphs
+    let mut next = lcg(3);
+
+    for _ in 0..30 {
+        let mut g: Graph<u64, u64> = Graph::new();
+
+        for i in 0..12 {
+            g.add_node(i);
+        }
+
+        let mut added = BTreeSet::new();
+
+        for _ in 0..14 {
+            let (u, v, w) = (next(12), next(12), Some(next(2)));
+            g.add_undirected_edge(&u, &v, w);
+
+            if u != v {
+                added.insert((u.min(v), u.max(v), w));
+            }
+        }
+
+        let components = g.weakly_connected_components().0.len();
+        let articulation_points = g.articulation_points();
+
+        for n in 0..12 {
+            let mut removed = g.clone();
+            removed.remove_node(&n);
+
+            let split = removed.weakly_connected_components().0.len() > components;
+            assert_eq!(articulation_points.contains(&n), split);
+        }
+
+        let bridges = g.bridges();
+
+        for (u, v, w) in added.iter().copied() {
+            let mut removed = g.clone();
+            removed.remove_edge(&u, &v, w);
+            removed.remove_edge(&v, &u, w);
+
+            let split = removed.weakly_connected_components().0.len() > components;
+            assert_eq!(bridges.contains(&(&u, &v, &w)), split);
+        }
+
+        // Biconnected components share at most one node, and every edge is inside one of them
+        let biconnected = g.biconnected_components();
+
+        for (i, a) in biconnected.iter().enumerate() {
+            for b in biconnected[i + 1..].iter() {
+                assert!(a.iter().filter(|n| b.contains(n)).count() <= 1);
+            }
+        }
+
+        for (u, v, _) in added.iter() {
+            assert!(biconnected.iter().any(|c| c.contains(&u) && c.contains(&v)));
+        }
+    }
+}
+
+#[test]
+fn test_eulerian_circuit() {
+    // Every street is swept along both sides, and one street is swept twice
+    let mut g: Graph<char, i32> = graph_with_nodes!('a', 'b', 'c', 'd', 'e');
+
+    g.add_undirected_edge(&'a', &'b', None);
+    g.add_undirected_edge(&'b', &'c', None);
+    g.add_undirected_edge(&'c', &'a', None);
+    g.add_undirected_edge(&'c', &'d', Some(1));
+    g.add_undirected_edge(&'c', &'d', Some(2));
+    g.add_edge(&'d', &'d', None);
+
+    let circuit = g.eulerian_circuit(&'c').unwrap();
+
+    assert_eq!(circuit.len(), g.num_edges());
+    assert_eq!(circuit.first().unwrap().0, &'c');
+    assert_eq!(circuit.last().unwrap().1, &'c');
+
+    for pair in circuit.windows(2) {
+        assert_eq!(pair[0].1, pair[1].0);
+    }
+
+    let used: BTreeSet<_> = circuit.iter().collect();
+    assert_eq!(used.len(), circuit.len());
+    assert!(circuit.iter().all(|(u, v, w)| g.is_edge(u, v, w)));
+
+    assert_eq!(
+        g.eulerian_circuit(&'e').unwrap_err(),
+        GraphError::Disconnected(&'e', &'a')
+    );
+    assert_eq!(
+        g.eulerian_circuit(&'f').unwrap_err(),
+        GraphError::NodeNotFound(&'f')
+    );
+
+    g.add_edge(&'d', &'e', None);
+    assert_eq!(
+        g.eulerian_circuit(&'a').unwrap_err(),
+        GraphError::UnbalancedDegree(&'d', 3, 4)
+    );
+
+    let g: Graph<i32, i32> = graph_with_nodes!(1);
+    assert_eq!(g.eulerian_circuit(&1).unwrap(), vec![]);
+}
+
+#[test]
+fn test_eulerian_path() {
+    let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4, 5);
+
+    g.add_edge(&1, &2, None);
+    g.add_edge(&2, &3, None);
+    g.add_edge(&3, &1, None);
+    g.add_edge(&3, &4, Some(1));
+    g.add_edge(&4, &3, None);
+    g.add_edge(&3, &4, Some(2));
+
+    let path = g.eulerian_path().unwrap();
+
+    assert_eq!(path.len(), 6);
+    assert_eq!(path.first().unwrap().0, &3);
+    assert_eq!(path.last().unwrap().1, &4);
+
+    for pair in path.windows(2) {
+        assert_eq!(pair[0].1, pair[1].0);
+    }
+
+    // A circuit is also a path
+    g.add_edge(&4, &5, None);
+    g.add_edge(&5, &3, None);
+
+    let path = g.eulerian_path().unwrap();
+    assert_eq!(path.len(), 8);
+    assert_eq!(path.first().unwrap().0, &1);
+    assert_eq!(path.last().unwrap().1, &1);
+
+    // Two nodes with an extra outgoing edge
+    g.add_edge(&1, &4, None);
+    g.add_edge(&2, &5, None);
+    assert_eq!(
+        g.eulerian_path().unwrap_err(),
+        GraphError::UnbalancedDegree(&2, 1, 2)
+    );
+
+    let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4);
+    g.add_edge(&1, &2, None);
+    g.add_edge(&3, &4, None);
+    g.add_edge(&4, &3, None);
+
+    assert_eq!(
+        g.eulerian_path().unwrap_err(),
+        GraphError::Disconnected(&1, &3)
+    );
+
+    g.add_edge(&1, &2, Some(5));
+    assert_eq!(
+        g.eulerian_path().unwrap_err(),
+        GraphError::UnbalancedDegree(&1, 0, 2)
+    );
+
+    let g: Graph<i32, i32> = graph_with_nodes!(1, 2);
+    assert_eq!(g.eulerian_path().unwrap(), vec![]);
+}
+
+#[test]
+fn test_travelling_salesman() {
+    let mut rng = lcg(11);
+    let mut next = |m: u64| rng(m) as i64;
+
+    for round in 0..10 {
+        let n = 8;
+        let points: Vec<(i64, i64)> = (0..n).map(|_| (next(100), next(100))).collect();
+        let mut g: Graph<usize, i64> = Graph::new();
+        let mut dist = vec![vec![None; n]; n];
+
+        for i in 0..n {
+            g.add_node(i);
+        }
+
+        // Manhattan distances are metric. Odd rounds drop some edges and make the rest one-way.
+        for u in 0..n {
+            for v in 0..n {
+                if u == v || (round % 2 == 1 && next(3) == 0) {
+                    continue;
+                }
+
+                let w = (points[u].0 - points[v].0).abs() + (points[u].1 - points[v].1).abs();
+                let w = if round % 2 == 1 { w + next(20) } else { w };
+
+                g.add_edge(&u, &v, Some(w));
+                dist[u][v] = Some(w);
+            }
+        }
+
+        let mut optimal = None;
+        brute_force_tour(&dist, &mut vec![0], &mut optimal);
+
+        let exact = g.held_karp(&0).unwrap();
+        assert_eq!(exact.as_ref().map(|t| t.cost), optimal);
+
+        let mut tours = vec![];
+        tours.extend(exact);
+
+        if let Some(tour) = g.nearest_neighbour_tour(&0).unwrap() {
+            let cost = tour.cost;
+            let improved = g.two_opt(tour).unwrap();
+
+            assert!(improved.cost <= cost);
+            tours.push(improved);
+        }
+
+        if round % 2 == 0 {
+            let tour = g.christofides(&0).unwrap().unwrap();
+            assert!(2 * tour.cost <= 3 * optimal.unwrap());
+            tours.push(tour);
+        } else {
+            assert!(g.christofides(&0).unwrap().is_none());
+        }
+
+        for tour in tours {
+            assert_eq!(tour.nodes.len(), n + 1);
+            assert_eq!(tour.nodes[0], &0);
+            assert_eq!(tour.nodes[n], &0);
+            assert_eq!(tour.nodes.iter().collect::<BTreeSet<_>>().len(), n);
+            assert!(tour.cost >= optimal.unwrap());
+
+            let cost: i64 = tour
+                .nodes
+                .windows(2)
+                .map(|pair| dist[*pair[0]][*pair[1]].unwrap())
+                .sum();
+
+            assert_eq!(cost, tour.cost);
+        }
+    }
+
+    // Enough odd degree nodes for Christofides to match them greedily
+    const { assert!(EXACT_MATCHING_LIMIT < 30) };
+
+    let mut g: Graph<i64, i64> = Graph::new();
+
+    for i in 0..30 {
+        g.add_node(i);
+    }
+
+    for u in 0..30 {
+        for v in u + 1..30 {
+            g.add_undirected_edge(&u, &v, Some(if u == 0 { 10 } else { 15 }));
+        }
+    }
+
+    let tour = g.christofides(&5).unwrap().unwrap();
+    assert_eq!(tour.nodes.iter().collect::<BTreeSet<_>>().len(), 30);
+    assert_eq!(tour.cost, 2 * 10 + 28 * 15);
+    assert_eq!(
+        g.held_karp(&5).unwrap_err(),
+        GraphError::TooManyNodes(30, HELD_KARP_LIMIT)
+    );
+
+    // Held-Karp takes graphs of up to 20 nodes
+    for i in HELD_KARP_LIMIT as i64 + 1..30 {
+        g.remove_node(&i);
+    }
+
+    assert_eq!(
+        g.held_karp(&5).unwrap_err(),
+        GraphError::TooManyNodes(21, 20)
+    );
+
+    let mut g: Graph<&str, i32> = graph_with_nodes!("Home");
+    let tour = g.held_karp(&"Home").unwrap().unwrap();
+    assert_eq!((&tour.nodes, tour.cost), (&vec![&"Home"], 0));
+
+    let tour = g.two_opt(tour).unwrap();
+    assert_eq!((&tour.nodes, tour.cost), (&vec![&"Home"], 0));
+
+    let stranger = Tour {
+        nodes: vec![&"Home", &"Away", &"Home"],
+        cost: 0,
+    };
+    assert_eq!(
+        g.two_opt(stranger).unwrap_err(),
+        GraphError::NodeNotFound(&"Away")
+    );
+
+    // Tours that don't visit every node exactly once and return to the start are rejected
+    let mut square: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4);
+
+    for u in 1..=4 {
+        for v in 1..=4 {
+            if u != v {
+                square.add_edge(&u, &v, Some(1));
+            }
+        }
+    }
+
+    let unclosed = Tour {
+        nodes: vec![&1, &2, &3],
+        cost: 2,
+    };
+    assert_eq!(
+        square.two_opt(unclosed).unwrap_err(),
+        GraphError::InvalidTour
+    );
+
+    let missing = Tour {
+        nodes: vec![&1, &2, &3, &1],
+        cost: 3,
+    };
+    assert_eq!(
+        square.two_opt(missing).unwrap_err(),
+        GraphError::InvalidTour
+    );
+
+    let repeated = Tour {
+        nodes: vec![&1, &2, &2, &3, &1],
+        cost: 0,
+    };
+    assert_eq!(
+        square.two_opt(repeated).unwrap_err(),
+        GraphError::DuplicateNode(&2)
+    );
+
+    let tour = square
+        .two_opt(Tour {
+            nodes: vec![&1, &3, &2, &4, &1],
+            cost: 0,
+        })
+        .unwrap();
+    assert_eq!((tour.nodes.len(), tour.cost), (5, 4));
+
+    square.remove_edge(&2, &4, Some(1));
+    let broken = Tour {
+        nodes: vec![&1, &3, &2, &4, &1],
+        cost: 4,
+    };
+    assert_eq!(square.two_opt(broken).unwrap_err(), GraphError::InvalidTour);
+    assert_eq!(
+        g.christofides(&"Work").unwrap_err(),
+        GraphError::NodeNotFound(&"Work")
+    );
+
+    g.add_node("Work");
+    g.add_edge(&"Home", &"Work", Some(5));
+    assert!(g.held_karp(&"Home").unwrap().is_none());
+    assert!(g.nearest_neighbour_tour(&"Home").unwrap().is_none());
+}
+
+#[test]
+fn test_coloring() {
+    let mut next = lcg(19);
+
+    // Checks that no two adjacent nodes share a color, and returns the number of colors used
+    fn check(g: &Graph<u64, i32>, colors: &HashMap<&u64, usize>) -> usize {
+        assert_eq!(colors.len(), g.num_nodes());
+
+        for u in g.nodes() {
+            for (v, _) in g.edges(u).unwrap().unwrap_or_default() {
+                if **u != *v {
+                    assert_ne!(colors[&**u], colors[v]);
+                }
+            }
+        }
+
+        colors.values().max().map_or(0, |c| c + 1)
+    }
+
+    for _ in 0..40 {
+        let n = next(8) + 1;
+        let mut g: Graph<u64, i32> = Graph::new();
+
+        for i in 0..n {
+            g.add_node(i);
+        }
+
+        for _ in 0..next(20) {
+            g.add_edge(&next(n), &next(n), None);
+        }
+
+        // The fewest colors is the smallest k for which some assignment of colors to nodes works
+        let edges: Vec<(u64, u64)> = g
+            .nodes()
+            .iter()
+            .flat_map(|u| {
+                g.edges(u)
+                    .unwrap()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(move |(v, _)| (**u, *v))
+            })
+            .filter(|(u, v)| u != v)
+            .collect();
+
+        let fewest = (1..=n)
+            .find(|k| {
+                (0..k.pow(n as u32)).any(|code| {
+                    let color = |u: u64| code / k.pow(u as u32) % k;
+                    edges.iter().all(|(u, v)| color(*u) != color(*v))
+                })
+            })
+            .unwrap() as usize;
+
+        let order: Vec<&u64> = g.nodes().iter().rev().map(|n| &**n).collect();
+        let greedy = check(&g, &g.greedy_coloring(&order).unwrap());
+        let dsatur = check(&g, &g.dsatur_coloring());
+        let (chromatic_number, colors) = g.chromatic_number();
+
+        assert_eq!(check(&g, &colors), chromatic_number);
+        assert_eq!(chromatic_number, fewest);
+        assert!(greedy >= fewest);
+        assert!(dsatur >= fewest);
+    }
+
+    // A crown graph needs two colors, but a bad order makes the greedy coloring use one per pair
+    let mut g: Graph<(u8, bool), i32> = Graph::new();
+
+    for i in 0..4 {
+        g.add_node((i, false));
+        g.add_node((i, true));
+    }
+
+    for i in 0..4 {
+        for j in 0..4 {
+            if i != j {
+                g.add_undirected_edge(&(i, false), &(j, true), None);
+            }
+        }
+    }
+
+    let order: Vec<(u8, bool)> = (0..4).flat_map(|i| [(i, false), (i, true)]).collect();
+    let order: Vec<&(u8, bool)> = order.iter().collect();
+    let greedy = g.greedy_coloring(&order).unwrap();
+
+    assert_eq!(greedy.values().max(), Some(&3));
+    assert_eq!(g.dsatur_coloring().values().max(), Some(&1));
+    assert_eq!(g.chromatic_number().0, 2);
+
+    // Exams sharing a student can't be in the same slot, and loops are ignored
+    let mut g: Graph<&str, i32> = graph_with_nodes!("Maths", "Physics", "Chemistry", "Art");
+    g.add_undirected_edge(&"Maths", &"Physics", None);
+    g.add_undirected_edge(&"Physics", &"Chemistry", None);
+    g.add_undirected_edge(&"Chemistry", &"Maths", None);
+    g.add_edge(&"Art", &"Art", None);
+
+    let (slots, colors) = g.chromatic_number();
+    assert_eq!(slots, 3);
+    assert_eq!(colors[&"Art"], 0);
+    assert_eq!(
+        g.greedy_coloring(&[&"History"]).unwrap_err(),
+        GraphError::NodeNotFound(&"History")
+    );
+
+    let g: Graph<i32, i32> = Graph::new();
+    assert_eq!(g.chromatic_number(), (0, HashMap::new()));
+    assert!(g.dsatur_coloring().is_empty());
+}
+
+#[test]
+fn test_cliques() {
+    let mut next = lcg(20);
+
+    for _ in 0..50 {
+        let n = next(10);
+        let mut g: Graph<u64, i32> = Graph::new();
+        let mut adjacent = vec![vec![false; n as usize]; n as usize];
+
+        for i in 0..n {
+            g.add_node(i);
+        }
+
+        for _ in 0..next(30) {
+            let (u, v) = (next(n.max(1)), next(n.max(1)));
+
+            if n > 0 {
+                g.add_edge(&u, &v, Some(next(3) as i32));
+                adjacent[u as usize][v as usize] = true;
+                adjacent[v as usize][u as usize] = true;
+            }
+        }
+
+        // Every subset of nodes that are pairwise adjacent, and that no other node is adjacent to all of
+        let is_clique = |mask: u64| {
+            (0..n).all(|u| {
+                (0..n).all(|v| {
+                    u == v
+                        || mask & (1 << u) == 0
+                        || mask & (1 << v) == 0
+                        || adjacent[u as usize][v as usize]
+                })
+            })
+        };
+
+        let expected: BTreeSet<BTreeSet<u64>> = (1..1u64 << n)
+            .filter(|mask| is_clique(*mask))
+            .filter(|mask| (0..n).all(|v| mask & (1 << v) != 0 || !is_clique(mask | (1 << v))))
+            .map(|mask| (0..n).filter(|v| mask & (1 << v) != 0).collect())
+            .collect();
+
+        let cliques: Vec<BTreeSet<u64>> = g
+            .maximal_cliques()
+            .map(|clique| clique.into_iter().copied().collect())
+            .collect();
+
+        assert_eq!(cliques.len(), expected.len());
+        assert_eq!(cliques.into_iter().collect::<BTreeSet<_>>(), expected);
+
+        let maximum = g.maximum_clique();
+        let largest = expected.iter().map(|c| c.len()).max().unwrap_or(0);
+
+        assert_eq!(maximum.len(), largest);
+        assert!(maximum.is_empty() || expected.contains(&maximum.into_iter().copied().collect()));
+    }
+
+    // Stopping early only finds the first cliques
+    let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4, 5, 6);
+    g.add_undirected_edge(&1, &2, None);
+    g.add_undirected_edge(&3, &4, None);
+    g.add_undirected_edge(&5, &6, None);
+
+    assert_eq!(g.maximal_cliques().take(2).count(), 2);
+    assert_eq!(g.maximum_clique().len(), 2);
+
+    let g: Graph<i32, i32> = Graph::new();
+    assert_eq!(g.maximal_cliques().next(), None);
+    assert!(g.maximum_clique().is_empty());
+}
+
+#[test]
+fn test_pagerank() {
+    let mut next = lcg(21);
+
+    // A dangling node passes its rank to every node
+    let mut g: Graph<&str, u32> = graph_with_nodes!("A", "B");
+    g.add_edge(&"A", &"B", None);
+
+    let ranks = g.pagerank(0.85, 1e-12, 1000);
+    assert!((ranks[&"A"] - 0.075 / 0.21375).abs() < 1e-9);
+    assert!((ranks[&"B"] - 1.85 * 0.075 / 0.21375).abs() < 1e-9);
+
+    for _ in 0..30 {
+        let n = next(8) + 1;
+        let mut g: Graph<u64, u32> = Graph::new();
+
+        for i in 0..n {
+            g.add_node(i);
+        }
+
+        for _ in 0..next(20) {
+            g.add_edge(&next(n), &next(n), Some(next(4) as u32));
+        }
+
+        let weight = |e: &Option<u32>| e.map_or(1.0, f64::from);
+        let ids: Vec<u64> = (0..=n / 2).collect();
+        let restart: HashMap<&u64, f64> = ids.iter().map(|i| (i, 2.0)).collect();
+
+        let uniform = g.weighted_pagerank(0.85, 1e-13, 10_000, weight);
+        let personalized = g
+            .personalized_pagerank(0.85, 1e-13, 10_000, &restart, weight)
+            .unwrap();
+
+        // Doubling every weight doesn't change where the surfer goes
+        let doubled = g.weighted_pagerank(0.85, 1e-13, 10_000, |e| 2.0 * weight(e));
+
+        for (ranks, jump) in [
+            (
+                &uniform,
+                (0..n).map(|_| 1.0 / n as f64).collect::<Vec<f64>>(),
+            ),
+            (
+                &personalized,
+                (0..n)
+                    .map(|i| {
+                        if i <= n / 2 {
+                            1.0 / (n / 2 + 1) as f64
+                        } else {
+                            0.0
+                        }
+                    })
+                    .collect(),
+            ),
+        ] {
+            assert_eq!(ranks.len(), n as usize);
+            assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+
+            // The ranks are a fixed point of one step of the surfer
+            let mut expected: Vec<f64> = jump.iter().map(|j| 0.15 * j).collect();
+
+            for u in 0..n {
+                let edges = g.edges(&u).unwrap().unwrap_or_default();
+                let total: f64 = edges.iter().map(|(_, e)| weight(e)).sum();
+
+                if total == 0.0 {
+                    for v in 0..n {
+                        expected[v as usize] += 0.85 * ranks[&u] * jump[v as usize];
+                    }
+                } else {
+                    for (v, e) in edges {
+                        expected[*v as usize] += 0.85 * ranks[&u] * weight(e) / total;
+                    }
+                }
+            }
+
+            for v in 0..n {
+                assert!((ranks[&v] - expected[v as usize]).abs() < 1e-9);
+            }
+        }
+
+        for v in 0..n {
+            assert!((uniform[&v] - doubled[&v]).abs() < 1e-9);
+        }
+    }
+
+    // Stopping after one iteration takes a single step from the uniform ranks
+    let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3);
+    g.add_edge(&1, &2, None);
+    g.add_edge(&2, &3, None);
+    g.add_edge(&3, &1, None);
+    g.add_edge(&3, &2, None);
+
+    let ranks = g.pagerank(0.5, 0.0, 1);
+    assert!((ranks[&1] - (0.5 / 3.0 + 0.5 / 6.0)).abs() < 1e-12);
+    assert!((ranks[&2] - (0.5 / 3.0 + 0.5 / 3.0 + 0.5 / 6.0)).abs() < 1e-12);
+
+    let restart = HashMap::from([(&4, 1.0)]);
+    assert_eq!(
+        g.personalized_pagerank(0.85, 1e-9, 100, &restart, |_| 1.0)
+            .unwrap_err(),
+        GraphError::NodeNotFound(&4)
+    );
+
+    // Without any restart chance, nodes are jumped to uniformly
+    let restart = HashMap::from([(&1, 0.0)]);
+    let ranks = g
+        .personalized_pagerank(0.85, 1e-12, 1000, &restart, |_| 1.0)
+        .unwrap();
+    let expected = g.pagerank(0.85, 1e-12, 1000);
+
+    for v in 1..=3 {
+        assert!((ranks[&v] - expected[&v]).abs() < 1e-12);
+    }
+
+    let g: Graph<i32, i32> = Graph::new();
+    assert!(g.pagerank(0.85, 1e-9, 100).is_empty());
+}
+
+#[test]
+fn test_centrality() {
+    let mut next = lcg(22);
+
+    for round in 0..30 {
+        let n = next(8) as usize + 1;
+        let mut g: Graph<usize, u64> = Graph::new();
+
+        for i in 0..n {
+            g.add_node(i);
+        }
+
+        // The lightest edge between each pair of distinct nodes
+        let mut weight: Vec<Vec<Option<u64>>> = vec![vec![None; n]; n];
+
+        for _ in 0..next(25) {
+            let (u, v) = (next(n as u64) as usize, next(n as u64) as usize);
+            let w = next(3) + 1;
+            let e = if round % 3 == 0 { None } else { Some(w) };
+
+            g.add_edge(&u, &v, e);
+
+            if u != v {
+                let w = e.unwrap_or(1);
+                weight[u][v] = Some(weight[u][v].map_or(w, |curr| curr.min(w)));
+            }
+        }
+
+        // Finds the distance and number of shortest paths between every pair with Floyd-Warshall
+        let shortest_paths = |weight: &Vec<Vec<Option<u64>>>| {
+            let mut dist: Vec<Vec<Option<u64>>> = weight.clone();
+
+            for (u, row) in dist.iter_mut().enumerate() {
+                row[u] = Some(0);
+            }
+
+            for k in 0..n {
+                for i in 0..n {
+                    for j in 0..n {
+                        if let (Some(a), Some(b)) = (dist[i][k], dist[k][j]) {
+                            if dist[i][j].is_none_or(|d| a + b < d) {
+                                dist[i][j] = Some(a + b);
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Paths to nodes in increasing order of distance
+            let mut count = vec![vec![0.0; n]; n];
+
+            for s in 0..n {
+                let mut order: Vec<usize> = (0..n).filter(|t| dist[s][*t].is_some()).collect();
+                order.sort_by_key(|t| dist[s][*t]);
+                count[s][s] = 1.0;
+
+                for t in order.into_iter().skip(1) {
+                    count[s][t] = (0..n)
+                        .filter(|u| match (dist[s][*u], weight[*u][t]) {
+                            (Some(d), Some(w)) => Some(d + w) == dist[s][t],
+                            _ => false,
+                        })
+                        .map(|u| count[s][u])
+                        .sum();
+                }
+            }
+
+            (dist, count)
+        };
+
+        let betweenness = |dist: &Vec<Vec<Option<u64>>>, count: &Vec<Vec<f64>>, v: usize| {
+            let mut total = 0.0;
+
+            for s in (0..n).filter(|s| *s != v) {
+                for t in (0..n).filter(|t| *t != v && *t != s) {
+                    if let (Some(a), Some(b), Some(d)) = (dist[s][v], dist[v][t], dist[s][t]) {
+                        if a + b == d {
+                            total += count[s][v] * count[v][t] / count[s][t];
+                        }
+                    }
+                }
+            }
+
+            total
+        };
+
+        let hops: Vec<Vec<Option<u64>>> = weight
+            .iter()
+            .map(|row| row.iter().map(|w| w.map(|_| 1)).collect())
+            .collect();
+
+        let (dist, count) = shortest_paths(&hops);
+        let (weighted_dist, weighted_count) = shortest_paths(&weight);
+
+        let unweighted = g.betweenness_centrality(false);
+        let normalized = g.betweenness_centrality(true);
+        let weighted = g.weighted_betweenness_centrality(false);
+        let closeness = g.closeness_centrality();
+        let harmonic = g.harmonic_centrality();
+
+        for v in 0..n {
+            assert!((unweighted[&v] - betweenness(&dist, &count, v)).abs() < 1e-9);
+            assert!((weighted[&v] - betweenness(&weighted_dist, &weighted_count, v)).abs() < 1e-9);
+
+            if n > 2 {
+                let pairs = ((n - 1) * (n - 2)) as f64;
+                assert!((normalized[&v] - unweighted[&v] / pairs).abs() < 1e-9);
+            }
+
+            let reached: Vec<u64> = (0..n)
+                .filter(|t| *t != v)
+                .filter_map(|t| dist[v][t])
+                .collect();
+            let total: u64 = reached.iter().sum();
+            let expected = if total == 0 {
+                0.0
+            } else {
+                let r = reached.len() as f64;
+                (r / total as f64) * (r / (n - 1) as f64)
+            };
+
+            assert!((closeness[&v] - expected).abs() < 1e-9);
+            assert!(
+                (harmonic[&v] - reached.iter().map(|d| 1.0 / *d as f64).sum::<f64>()).abs() < 1e-9
+            );
+        }
+    }
+
+    // Eigenvector centrality is an eigenvector of the adjacency matrix
+    let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2, 3, 4, 5);
+
+    for (u, v) in [(1, 2), (1, 3), (2, 3), (3, 4), (4, 5)] {
+        g.add_undirected_edge(&u, &v, None);
+    }
+
+    let scores = g.eigenvector_centrality(1e-14, 10_000);
+    let neighbours = |v: i32| -> f64 {
+        (1..=5)
+            .filter(|u| g.is_edge(u, &v, &None))
+            .map(|u| scores[&u])
+            .sum()
+    };
+    let eigenvalue = neighbours(3) / scores[&3];
+
+    assert!((scores.values().map(|x| x * x).sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(scores[&3] > scores[&1]);
+
+    for v in 1..=5 {
+        assert!((neighbours(v) - eigenvalue * scores[&v]).abs() < 1e-6);
+    }
+
+    // Hubs point to authorities, and authorities are pointed to by hubs
+    let mut g: Graph<&str, i32> = graph_with_nodes!("A", "B", "C", "X", "Y", "Lonely");
+
+    for (u, v) in [("A", "X"), ("A", "Y"), ("B", "X"), ("C", "Y"), ("C", "X")] {
+        g.add_edge(&u, &v, None);
+    }
+
+    let (hubs, authorities) = g.hits(1e-14, 10_000);
+
+    assert!((hubs.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!((authorities.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!((hubs[&"A"] - hubs[&"C"]).abs() < 1e-9);
+    assert!(hubs[&"A"] > hubs[&"B"]);
+    assert!(authorities[&"X"] > authorities[&"Y"]);
+    assert_eq!(hubs[&"X"], 0.0);
+    assert_eq!(authorities[&"A"], 0.0);
+    assert_eq!((hubs[&"Lonely"], authorities[&"Lonely"]), (0.0, 0.0));
+
+    let g: Graph<i32, i32> = graph_with_nodes!(1, 2);
+    let (hubs, authorities) = g.hits(1e-9, 100);
+    assert_eq!(authorities[&1], 0.0);
+    assert_eq!(hubs[&2], 0.0);
+    assert_eq!(g.betweenness_centrality(true)[&1], 0.0);
+    assert_eq!(g.closeness_centrality()[&1], 0.0);
+
+    let g: Graph<i32, i32> = Graph::new();
+    assert!(g.eigenvector_centrality(1e-9, 100).is_empty());
+    assert!(g.weighted_betweenness_centrality(true).is_empty());
+}
+
+#[test]
+fn test_communities() {
+    let mut next = lcg(23);
+
+    for round in 0..40 {
+        let n = next(10) + 1;
+        let mut g: Graph<u64, u64> = Graph::new();
+        let mut adjacent = vec![vec![0.0; n as usize]; n as usize];
+
+        for i in 0..n {
+            g.add_node(i);
+        }
+
+        for _ in 0..next(25) {
+            let (u, v, w) = (next(n), next(n), next(3));
+
+            if u != v && !g.is_edge(&u, &v, &Some(w)) && !g.is_edge(&v, &u, &Some(w)) {
+                adjacent[u as usize][v as usize] += 1.0;
+                adjacent[v as usize][u as usize] += 1.0;
+            }
+
+            if next(2) == 0 {
+                g.add_undirected_edge(&u, &v, Some(w));
+            } else {
+                g.add_edge(&u, &v, Some(w));
+            }
+        }
+
+        // Modularity straight from its definition, summing over pairs of nodes in the same community
+        let degree: Vec<f64> = adjacent.iter().map(|row| row.iter().sum()).collect();
+        let total: f64 = degree.iter().sum();
+        let expected = |communities: &Vec<Vec<&u64>>| {
+            if total == 0.0 {
+                return 0.0;
+            }
+
+            let mut q = 0.0;
+
+            for community in communities {
+                for u in community {
+                    for v in community {
+                        let (u, v) = (**u as usize, **v as usize);
+                        q += adjacent[u][v] - degree[u] * degree[v] / total;
+                    }
+                }
+            }
+
+            q / total
+        };
+
+        let louvain = g.louvain_communities(1.0);
+        let propagated = g.label_propagation(round);
+
+        for partition in [&louvain, &propagated] {
+            let mut all: Vec<&u64> = partition.communities.concat();
+            all.sort();
+
+            assert_eq!(all, (0..n).collect::<Vec<_>>().iter().collect::<Vec<_>>());
+            assert!((partition.modularity - expected(&partition.communities)).abs() < 1e-9);
+            assert!(
+                (partition.modularity - g.modularity(&partition.communities).unwrap()).abs() < 1e-9
+            );
+        }
+
+        // Louvain is at least as good as leaving every node alone, or putting them all together
+        let singletons: Vec<Vec<&u64>> = g.nodes().iter().map(|n| vec![&**n]).collect();
+        assert!(louvain.modularity >= expected(&singletons) - 1e-9);
+        assert!(louvain.modularity >= -1e-9);
+        assert!((g.modularity(&[]).unwrap() - expected(&singletons)).abs() < 1e-9);
+
+        // Every node ends up with one of the most common labels among its neighbours
+        for community in propagated.communities.iter() {
+            for u in community {
+                let count = |c: &Vec<&u64>| -> f64 {
+                    c.iter().map(|v| adjacent[**u as usize][**v as usize]).sum()
+                };
+
+                for other in propagated.communities.iter() {
+                    assert!(count(community) >= count(other));
+                }
+            }
+        }
+
+        assert_eq!(propagated, g.label_propagation(round));
+    }
+
+    // A ring of cliques joined by single edges splits into the cliques
+    let mut g: Graph<(u32, u32), i32> = Graph::new();
+
+    for c in 0..6 {
+        for i in 0..5 {
+            g.add_node((c, i));
+
+            for j in 0..i {
+                g.add_undirected_edge(&(c, i), &(c, j), None);
+            }
+        }
+    }
+
+    for c in 0..6 {
+        g.add_undirected_edge(&(c, 0), &((c + 1) % 6, 1), None);
+    }
+
+    let cliques: Vec<Vec<(u32, u32)>> = (0..6).map(|c| (0..5).map(|i| (c, i)).collect()).collect();
+    let cliques: Vec<Vec<&(u32, u32)>> = cliques.iter().map(|c| c.iter().collect()).collect();
+
+    let louvain = g.louvain_communities(1.0);
+    assert_eq!(louvain.communities, cliques);
+    assert!((louvain.modularity - g.modularity(&cliques).unwrap()).abs() < 1e-12);
+    assert_eq!(g.label_propagation(7).communities, cliques);
+
+    // A low resolution merges neighbouring cliques, and a high one splits them up
+    assert!(g.louvain_communities(0.05).communities.len() < 6);
+    assert!(g.louvain_communities(10.0).communities.len() > 6);
+
+    assert_eq!(
+        g.modularity(&[vec![&(9, 9)]]).unwrap_err(),
+        GraphError::NodeNotFound(&(9, 9))
+    );
+
+    let g: Graph<i32, i32> = graph_with_nodes!(1, 2);
+    let partition = g.louvain_communities(1.0);
+    assert_eq!(partition.communities, vec![vec![&1], vec![&2]]);
+    assert_eq!(partition.modularity, 0.0);
+
+    let g: Graph<i32, i32> = Graph::new();
+    assert!(g.label_propagation(0).communities.is_empty());
+}
+
+#[test]
+fn test_clustering() {
+    let mut next = lcg(24);
+
+    for _ in 0..40 {
+        let n = next(9) as usize + 1;
+        let mut g: Graph<usize, u8> = Graph::new();
+        let mut a = vec![vec![0usize; n]; n];
+
+        for i in 0..n {
+            g.add_node(i);
+        }
+
+        for _ in 0..next(30) {
+            let (u, v) = (next(n as u64) as usize, next(n as u64) as usize);
+            g.add_edge(&u, &v, Some(next(2) as u8));
+
+            if u != v {
+                a[u][v] = 1;
+            }
+        }
+
+        // The directed triangles through a node are half the closed walks of length three in the
+        // symmetrised adjacency matrix
+        let s: Vec<Vec<usize>> = (0..n)
+            .map(|i| (0..n).map(|j| a[i][j] + a[j][i]).collect())
+            .collect();
+        let connected = |i: usize, j: usize| s[i][j] > 0;
+
+        let mut triangles = vec![0; n];
+        let mut directed = vec![0; n];
+        let mut clustering = vec![0.0; n];
+        let mut directed_clustering = vec![0.0; n];
+        let (mut paths, mut possible) = (0, 0);
+
+        for i in 0..n {
+            let neighbours: Vec<usize> = (0..n).filter(|j| connected(i, *j)).collect();
+            let d = neighbours.len();
+
+            for (x, j) in neighbours.iter().enumerate() {
+                for k in neighbours.iter().skip(x + 1) {
+                    if connected(*j, *k) {
+                        triangles[i] += 1;
+                    }
+                }
+            }
+
+            for j in 0..n {
+                for k in 0..n {
+                    directed[i] += s[i][j] * s[j][k] * s[k][i];
+                }
+            }
+
+            directed[i] /= 2;
+
+            let total_degree: usize = (0..n).map(|j| a[i][j] + a[j][i]).sum();
+            let reciprocal: usize = (0..n).map(|j| a[i][j] * a[j][i]).sum();
+            let pairs = total_degree * total_degree.saturating_sub(1) - 2 * reciprocal;
+
+            if d > 1 {
+                clustering[i] = 2.0 * triangles[i] as f64 / (d * (d - 1)) as f64;
+            }
+
+            if pairs > 0 {
+                directed_clustering[i] = directed[i] as f64 / pairs as f64;
+            }
+
+            paths += d * d.saturating_sub(1);
+            possible += pairs;
+        }
+
+        assert_eq!(g.triangle_count(), triangles.iter().sum::<usize>() / 3);
+        assert_eq!(
+            g.directed_triangle_count(),
+            directed.iter().sum::<usize>() / 3
+        );
+
+        let local = g.local_clustering();
+        let directed_local = g.directed_local_clustering();
+
+        for i in 0..n {
+            assert!((local[&i] - clustering[i]).abs() < 1e-12);
+            assert!((directed_local[&i] - directed_clustering[i]).abs() < 1e-12);
+            assert!(directed_local[&i] <= 1.0);
+        }
+
+        let average = |values: &Vec<f64>| values.iter().sum::<f64>() / n as f64;
+        let ratio = |a: usize, b: usize| if b == 0 { 0.0 } else { a as f64 / b as f64 };
+
+        assert!((g.average_clustering() - average(&clustering)).abs() < 1e-12);
+        assert!((g.directed_average_clustering() - average(&directed_clustering)).abs() < 1e-12);
+        assert!(
+            (g.transitivity() - ratio(2 * triangles.iter().sum::<usize>(), paths)).abs() < 1e-12
+        );
+        assert!((g.directed_transitivity() - ratio(directed.iter().sum(), possible)).abs() < 1e-12);
+    }
+
+    // Every set of three nodes in a complete graph is a triangle
+    let mut g: Graph<u32, u32> = Graph::new();
+
+    for i in 0..30 {
+        g.add_node(i);
+
+        for j in 0..i {
+            g.add_undirected_edge(&i, &j, None);
+        }
+    }
+
+    assert_eq!(g.triangle_count(), 30 * 29 * 28 / 6);
+    assert_eq!(g.directed_triangle_count(), 8 * 30 * 29 * 28 / 6);
+    assert_eq!(g.average_clustering(), 1.0);
+    assert_eq!(g.directed_transitivity(), 1.0);
+
+    // Loops and parallel edges don't make triangles
+    let mut g: Graph<i32, i32> = graph_with_nodes!(1, 2);
+    g.add_edge(&1, &1, None);
+    g.add_edge(&1, &2, Some(1));
+    g.add_edge(&1, &2, Some(2));
+
+    assert_eq!(g.triangle_count(), 0);
+    assert_eq!(g.local_clustering()[&1], 0.0);
+    assert_eq!(g.transitivity(), 0.0);
+
+    let g: Graph<i32, i32> = Graph::new();
+    assert_eq!(g.average_clustering(), 0.0);
+    assert_eq!(g.directed_average_clustering(), 0.0);
+}
+
+#[test]
+fn test_dominators() {
+    let mut next = lcg(25);
+
+    for _ in 0..60 {
+        let n = next(10) as usize + 1;
+        let mut g: Graph<usize, u8> = Graph::new();
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+
+        for i in 0..n {
+            g.add_node(i);
+        }
+
+        for _ in 0..next(25) {
+            let (u, v) = (next(n as u64) as usize, next(n as u64) as usize);
+            g.add_edge(&u, &v, Some(next(2) as u8));
+            edges.push((u, v));
+        }
+
+        // The nodes reachable from node 0 without passing through `removed`
+        let reachable = |removed: Option<usize>| {
+            let mut seen = vec![false; n];
+            let mut stack = vec![0];
+
+            if removed == Some(0) {
+                return seen;
+            }
+
+            seen[0] = true;
+
+            while let Some(u) = stack.pop() {
+                for (_, v) in edges.iter().filter(|(a, _)| *a == u) {
+                    if !seen[*v] && Some(*v) != removed {
+                        seen[*v] = true;
+                        stack.push(*v);
+                    }
+                }
+            }
+
+            seen
+        };
+
+        let reached = reachable(None);
+        let without: Vec<Vec<bool>> = (0..n).map(|a| reachable(Some(a))).collect();
+        let dominates = |a: usize, b: usize| reached[b] && (a == b || !without[a][b]);
+
+        let dominators = g.dominators(&0).unwrap();
+
+        assert_eq!(dominators.root, &0);
+        assert_eq!(
+            dominators.idom.len(),
+            reached.iter().filter(|r| **r).count()
+        );
+
+        for b in 0..n {
+            for a in 0..n {
+                assert_eq!(dominators.dominates(&a, &b), dominates(a, b));
+            }
+
+            if !reached[b] {
+                assert!(!dominators.idom.contains_key(&b));
+                assert!(dominators.dominance_frontier(&b).is_none());
+                continue;
+            }
+
+            // The immediate dominator is the strict dominator that every other strict dominator dominates
+            let strict: Vec<usize> = (0..n).filter(|a| *a != b && dominates(*a, b)).collect();
+            let idom = strict
+                .iter()
+                .find(|d| strict.iter().all(|a| dominates(*a, **d)));
+
+            assert_eq!(dominators.idom[&b], idom);
+
+            let frontier: BTreeSet<&usize> = (0..n)
+                .filter(|y| {
+                    edges
+                        .iter()
+                        .any(|(p, v)| v == y && reached[*p] && dominates(b, *p))
+                        && !(b != *y && dominates(b, *y))
+                })
+                .map(|y| g.nodes().iter().find(|n| ***n == y).map(|n| &**n).unwrap())
+                .collect();
+
+            assert_eq!(dominators.dominance_frontier(&b), Some(&frontier));
+        }
+    }
+
+    // A loop back to the entry puts the entry in the frontier of the loop
+    let mut g: Graph<&str, i32> = graph_with_nodes!("entry", "body", "dead");
+    g.add_edge(&"entry", &"body", None);
+    g.add_edge(&"body", &"entry", None);
+    g.add_edge(&"dead", &"body", None);
+
+    let dominators = g.dominators(&"entry").unwrap();
+    assert_eq!(
+        dominators.dominance_frontier(&"body"),
+        Some(&BTreeSet::from([&"entry"]))
+    );
+    assert_eq!(
+        dominators.dominance_frontier(&"entry"),
+        Some(&BTreeSet::from([&"entry"]))
+    );
+    assert!(!dominators.dominates(&"dead", &"body"));
+
+    assert_eq!(
+        g.dominators(&"exit").unwrap_err(),
+        GraphError::NodeNotFound(&"exit")
+    );
+}